@@ -0,0 +1,107 @@
+use core::task::{Context, Poll};
+use core::pin::Pin;
+use core::cmp;
+use crate::ErrorType;
+use super::{AsyncRead, AsyncBufRead};
+
+#[derive(Debug)]
+pub struct AsyncBufReader<R, const N: usize> {
+    inner: R,
+    buffer: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+impl<R, const N: usize> AsyncBufReader<R, N> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: [0u8; N],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        unsafe {
+            self.map_unchecked_mut(|this| &mut this.inner)
+        }
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.pos <= self.filled && self.filled <= N);
+            self.buffer.get_unchecked(self.pos..self.filled)
+        }
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Any buffered data that has not yet been read is discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: ErrorType, const N: usize> ErrorType for AsyncBufReader<R, N> {
+    type Error = R::Error;
+}
+
+impl<R: AsyncRead, const N: usize> AsyncRead for AsyncBufReader<R, N> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+        // bypass the internal buffer entirely for large reads
+        if self.pos == self.filled && buf.len() >= N {
+            let this = unsafe { self.get_unchecked_mut() };
+            this.pos = 0;
+            this.filled = 0;
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            return inner.poll_read(cx, buf)
+        }
+
+        let len = match self.as_mut().poll_fill_buf(cx)? {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(mut rem) => crate::slice_read(&mut rem, buf),
+        };
+        self.consume(len);
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl<R: AsyncRead, const N: usize> AsyncBufRead for AsyncBufReader<R, N> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.pos >= this.filled {
+            debug_assert_eq!(this.pos, this.filled);
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            let len = match inner.poll_read(cx, &mut this.buffer)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(len) => len,
+            };
+            debug_assert!(len <= N);
+            this.filled = cmp::min(len, N);
+            this.pos = 0;
+        }
+        Poll::Ready(Ok(this.buffer()))
+    }
+
+    #[inline]
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.pos = cmp::min(this.pos.saturating_add(amt), this.filled);
+    }
+}
@@ -0,0 +1,213 @@
+use core::future::Future;
+use core::task::{Context, Poll};
+use core::pin::Pin;
+use core::cmp;
+use crate::{AllError, ErrorType, IntoInnerError};
+use super::{AsyncWrite, retry_interrupted};
+
+/// Buffered data is only written out when the buffer fills up, or on
+/// `poll_flush` and `poll_close`.
+#[derive(Debug)]
+pub struct AsyncBufWriter<W, const N: usize> {
+    inner: W,
+    buffer: [u8; N],
+    filled: usize,
+}
+
+impl<W, const N: usize> AsyncBufWriter<W, N> {
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: [0u8; N],
+            filled: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        unsafe {
+            self.map_unchecked_mut(|this| &mut this.inner)
+        }
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.filled <= N);
+            self.buffer.get_unchecked(..self.filled)
+        }
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn spare_capacity(&self) -> usize {
+        N - self.filled
+    }
+
+    fn write_to_buf(&mut self, buf: &[u8]) -> usize {
+        let len = cmp::min(buf.len(), self.spare_capacity());
+        unsafe {
+            self.buffer.get_unchecked_mut(self.filled..self.filled + len)
+                .copy_from_slice(buf.get_unchecked(..len));
+        }
+        self.filled += len;
+        len
+    }
+}
+
+/// Flushing fails with `AllError::WriteZero` if the inner writer stops
+/// accepting data.
+impl<W: ErrorType, const N: usize> ErrorType for AsyncBufWriter<W, N> {
+    type Error = AllError<W::Error>;
+}
+
+impl<W: AsyncWrite, const N: usize> AsyncWrite for AsyncBufWriter<W, N> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+        if buf.len() > self.spare_capacity() {
+            match self.as_mut().poll_flush_buf(cx)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(()) => (),
+            }
+        }
+
+        let this = unsafe { self.get_unchecked_mut() };
+        // bypass the internal buffer entirely for large writes
+        if buf.len() >= N && this.filled == 0 {
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            return inner.poll_write(cx, buf).map_err(From::from)
+        }
+
+        Poll::Ready(Ok(this.write_to_buf(buf)))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        match self.as_mut().poll_flush_buf(cx)? {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => self.get_pin_mut().poll_flush(cx).map_err(From::from),
+        }
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        match self.as_mut().poll_flush_buf(cx)? {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => self.get_pin_mut().poll_close(cx).map_err(From::from),
+        }
+    }
+}
+
+impl<W: AsyncWrite, const N: usize> AsyncBufWriter<W, N> {
+    fn poll_flush_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), AllError<W::Error>>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+        let mut written = 0usize;
+        let res = loop {
+            let buf = unsafe {
+                debug_assert!(written <= this.filled);
+                this.buffer.get_unchecked(written..this.filled)
+            };
+            if buf.is_empty() {
+                break Poll::Ready(Ok(()))
+            }
+            match retry_interrupted(inner.as_mut().poll_write(cx, buf), cx) {
+                Poll::Pending => break Poll::Pending,
+                Poll::Ready(Ok(0)) => break Poll::Ready(Err(AllError::WriteZero)),
+                Poll::Ready(Ok(n)) => written += cmp::min(n, buf.len()),
+                Poll::Ready(Err(e)) => break Poll::Ready(Err(AllError::Io(e))),
+            }
+        };
+        this.buffer.copy_within(written..this.filled, 0);
+        this.filled -= written;
+        res
+    }
+
+    /// Flushes the buffer and unwraps the inner writer.
+    ///
+    /// On failure the returned error still holds the writer along with any
+    /// bytes that could not be written out.
+    pub fn into_inner(self) -> AsyncIntoInner<W, N> where W: Unpin {
+        AsyncIntoInner {
+            writer: Some(self),
+        }
+    }
+}
+
+pub struct AsyncIntoInner<W, const N: usize> {
+    writer: Option<AsyncBufWriter<W, N>>,
+}
+
+impl<W: AsyncWrite + Unpin, const N: usize> Future for AsyncIntoInner<W, N> {
+    type Output = Result<W, IntoInnerError<AsyncBufWriter<W, N>, AllError<W::Error>>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut writer = self.writer.take().expect("AsyncIntoInner polled after completion");
+        match Pin::new(&mut writer).poll_flush_buf(cx) {
+            Poll::Pending => {
+                self.writer = Some(writer);
+                Poll::Pending
+            },
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(writer.inner)),
+            Poll::Ready(Err(error)) => Poll::Ready(Err(IntoInnerError::new(writer, error))),
+        }
+    }
+}
+
+impl<W, const N: usize, E> IntoInnerError<AsyncBufWriter<W, N>, E> {
+    /// The bytes that were still buffered when the final flush failed.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        self.writer().buffer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::Future;
+    use core::task::{Context, Poll, Waker};
+    use core::pin::Pin;
+    use crate::{AllError, AsyncWrite};
+    use super::AsyncBufWriter;
+
+    #[test]
+    fn into_inner_flushes_first() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut out = [0u8; 4];
+        let mut writer = AsyncBufWriter::<_, 4>::new(&mut out[..]);
+        assert_eq!(Pin::new(&mut writer).poll_write(&mut cx, b"abc"), Poll::Ready(Ok(3)));
+        let mut into_inner = writer.into_inner();
+        match Pin::new(&mut into_inner).poll(&mut cx) {
+            Poll::Ready(Ok(rest)) => assert_eq!(rest.len(), 1),
+            _ => panic!("expected the writer back"),
+        }
+        assert_eq!(&out[..3], b"abc");
+    }
+
+    #[test]
+    fn into_inner_hands_back_unflushed_bytes() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut out = [0u8; 2];
+        let mut writer = AsyncBufWriter::<_, 4>::new(&mut out[..]);
+        assert_eq!(Pin::new(&mut writer).poll_write(&mut cx, b"abc"), Poll::Ready(Ok(3)));
+        let mut into_inner = writer.into_inner();
+        match Pin::new(&mut into_inner).poll(&mut cx) {
+            Poll::Ready(Err(e)) => {
+                assert_eq!(e.error(), &AllError::Io(AllError::UnexpectedEof));
+                assert_eq!(e.buffer(), b"c");
+            },
+            _ => panic!("expected the final flush to fail"),
+        }
+    }
+}
@@ -4,6 +4,7 @@ use core::pin::Pin;
 use core::marker::PhantomData;
 use unchecked_ops::*;
 use crate::AllError;
+use super::retry_interrupted;
 
 pub struct AsyncCopy<'a, 'b, R: ?Sized, W: ?Sized, E> {
     read: Pin<&'a mut R>,
@@ -41,7 +42,7 @@ enum State {
 }
 
 impl<'a, 'b, R: ?Sized + super::AsyncRead, W: ?Sized + super::AsyncWrite, E: From<R::Error> + From<W::Error>> AsyncCopy<'a, 'b, R, W, E> {
-    fn do_read(&mut self, cx: &mut Context) -> Result<State, E> {
+    fn do_read(&mut self, cx: &mut Context) -> Result<State, AllError<E>> {
         if self.eof {
             return Ok(State::Eof)
         }
@@ -51,7 +52,8 @@ impl<'a, 'b, R: ?Sized + super::AsyncRead, W: ?Sized + super::AsyncWrite, E: Fro
             return Ok(State::Buffer)
         }
 
-        match self.read.as_mut().poll_read(cx, buffer)? {
+        let total = self.total;
+        match retry_interrupted(self.read.as_mut().poll_read(cx, buffer), cx).map_err(|e| AllError::from_io(e, total))? {
             Poll::Pending => Ok(State::Pending),
             Poll::Ready(0) => {
                 self.eof = true;
@@ -77,9 +79,10 @@ impl<'a, 'b, R: ?Sized + super::AsyncRead, W: ?Sized + super::AsyncWrite, E: Fro
             }
         }
 
-        match self.write.as_mut().poll_write(cx, buffer).map_err(E::from)? {
+        let total = self.total;
+        match retry_interrupted(self.write.as_mut().poll_write(cx, buffer), cx).map_err(|e| AllError::from_io(e, total))? {
             Poll::Pending => Ok(State::Pending),
-            Poll::Ready(0) => Err(AllError::UnexpectedEof),
+            Poll::Ready(0) => Err(AllError::WriteZero),
             Poll::Ready(len) => {
                 unsafe {
                     debug_assert!(len <= buffer.len());
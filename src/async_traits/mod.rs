@@ -1,51 +1,110 @@
-use core::convert::Infallible;
 use core::task::{Context, Poll};
 use core::ops::DerefMut;
+use core::convert::TryFrom;
 use core::pin::Pin;
-use crate::{Take, AllError};
+use crate::{ErrorType, IoError, ErrorKind, Take, Chain, Fuse, SeekFrom, InvalidSeek, IoSlice, IoSliceMut, ReadBuf};
 
 pub(crate) mod prelude {
     pub use super::{
-        AsyncRead, AsyncReadExt,
+        AsyncRead, AsyncReadExt, AsyncBufRead,
         AsyncWrite, AsyncWriteExt,
         AsyncSynchronous, AsyncSynchronousExt,
+        AsyncSeek, AsyncSeekExt,
+        AsyncReadAt, AsyncReadAtExt, AsyncWriteAt, AsyncWriteAtExt,
     };
 }
 
-pub trait AsyncRead {
-    type Error;
-
+pub trait AsyncRead: ErrorType {
     fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>>;
-}
 
-pub trait AsyncWrite {
-    type Error;
+    /// Reads into the unfilled part of `buf`. The default implementation only
+    /// zeroes whatever part of it has not been initialized yet.
+    fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        let len = match self.poll_read(cx, buf.initialize_unfilled())? {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(len) => len,
+        };
+        buf.advance(len);
+        Poll::Ready(Ok(()))
+    }
 
+    /// The default implementation reads into the first non-empty buffer.
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+        match bufs.iter_mut().find(|b| !b.is_empty()) {
+            Some(buf) => self.poll_read(cx, buf),
+            None => self.poll_read(cx, &mut []),
+        }
+    }
+}
+
+pub trait AsyncWrite: ErrorType {
     fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>>;
 
+    /// The default implementation writes out the first non-empty buffer.
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+        match bufs.iter().find(|b| !b.is_empty()) {
+            Some(buf) => self.poll_write(cx, buf),
+            None => self.poll_write(cx, &[]),
+        }
+    }
+
+    /// Whether `poll_write_vectored` does better than the default implementation.
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>>;
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>>;
 }
 
-pub trait AsyncSynchronous {
+pub trait AsyncSynchronous: ErrorType {
+    fn poll_read_write(self: Pin<&mut Self>, context: &mut Context, buffer: &mut [u8]) -> Poll<Result<usize, Self::Error>>;
+}
+
+pub trait AsyncBufRead: AsyncRead {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>>;
+
+    fn consume(self: Pin<&mut Self>, amt: usize);
+}
+
+pub trait AsyncSeek {
     type Error;
 
-    fn poll_read_write(self: Pin<&mut Self>, context: &mut Context, buffer: &mut [u8]) -> Poll<Result<usize, Self::Error>>;
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>>;
 }
 
 impl AsyncRead for &'_ [u8] {
-    type Error = Infallible;
-
     #[inline]
     fn poll_read(self: Pin<&mut Self>, _: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         let this = self.get_mut();
         Poll::Ready(Ok(crate::slice_read(this, buf)))
     }
+
+    #[inline]
+    fn poll_read_buf(self: Pin<&mut Self>, _: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        let len = core::cmp::min(this.len(), buf.remaining());
+        let (head, tail) = this.split_at(len);
+        buf.put_slice(head);
+        *this = tail;
+        Poll::Ready(Ok(()))
+    }
 }
 
-impl AsyncWrite for &'_ mut [u8] {
-    type Error = AllError<Infallible>;
+impl AsyncBufRead for &'_ [u8] {
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+        Poll::Ready(Ok(*self.get_mut()))
+    }
 
+    #[inline]
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        *this = this.get(amt..).unwrap_or(&[]);
+    }
+}
+
+impl AsyncWrite for &'_ mut [u8] {
     #[inline]
     fn poll_write(self: Pin<&mut Self>, _: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
         let this = self.get_mut();
@@ -64,22 +123,50 @@ impl AsyncWrite for &'_ mut [u8] {
 }
 
 impl<T: ?Sized + AsyncRead + Unpin> AsyncRead for &'_ mut T {
-    type Error = T::Error;
-
     #[inline]
     fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         Pin::new(&mut **self).poll_read(cx, buf)
     }
+
+    #[inline]
+    fn poll_read_buf(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_read_buf(cx, buf)
+    }
+
+    #[inline]
+    fn poll_read_vectored(mut self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_read_vectored(cx, bufs)
+    }
 }
 
-impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for &'_ mut T {
-    type Error = T::Error;
+impl<T: ?Sized + AsyncBufRead + Unpin> AsyncBufRead for &'_ mut T {
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+        Pin::new(&mut **self.get_mut()).poll_fill_buf(cx)
+    }
 
+    #[inline]
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut **self).consume(amt)
+    }
+}
+
+impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for &'_ mut T {
     #[inline]
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
         Pin::new(&mut **self).poll_write(cx, buf)
     }
 
+    #[inline]
+    fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_write_vectored(cx, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
     #[inline]
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
         Pin::new(&mut **self).poll_flush(cx)
@@ -91,23 +178,62 @@ impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for &'_ mut T {
     }
 }
 
-impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncRead> AsyncRead for Pin<P> {
+impl<T: ?Sized + AsyncSeek + Unpin> AsyncSeek for &'_ mut T {
     type Error = T::Error;
 
+    #[inline]
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>> {
+        Pin::new(&mut **self).poll_seek(cx, pos)
+    }
+}
+
+impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncRead> AsyncRead for Pin<P> {
     #[inline]
     fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         self.get_mut().as_mut().poll_read(cx, buf)
     }
+
+    #[inline]
+    fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().as_mut().poll_read_buf(cx, buf)
+    }
+
+    #[inline]
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+        self.get_mut().as_mut().poll_read_vectored(cx, bufs)
+    }
 }
 
-impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncWrite> AsyncWrite for Pin<P> {
-    type Error = T::Error;
+impl<P: DerefMut + Unpin> AsyncBufRead for Pin<P> where
+    P::Target: AsyncBufRead,
+{
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+        self.get_mut().as_mut().poll_fill_buf(cx)
+    }
 
+    #[inline]
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().as_mut().consume(amt)
+    }
+}
+
+impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncWrite> AsyncWrite for Pin<P> {
     #[inline]
     fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
         self.get_mut().as_mut().poll_write(cx, buf)
     }
 
+    #[inline]
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+        self.get_mut().as_mut().poll_write_vectored(cx, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
     #[inline]
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
         self.get_mut().as_mut().poll_flush(cx)
@@ -119,25 +245,63 @@ impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncWrite> AsyncWrite for Pin<P
     }
 }
 
-#[cfg(feature = "std")]
-impl<T: ?Sized + AsyncRead + Unpin> AsyncRead for Box<T> {
+impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncSeek> AsyncSeek for Pin<P> {
     type Error = T::Error;
 
+    #[inline]
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>> {
+        self.get_mut().as_mut().poll_seek(cx, pos)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized + AsyncRead + Unpin> AsyncRead for Box<T> {
     #[inline]
     fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         Pin::new(&mut **self).poll_read(cx, buf)
     }
+
+    #[inline]
+    fn poll_read_buf(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_read_buf(cx, buf)
+    }
+
+    #[inline]
+    fn poll_read_vectored(mut self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_read_vectored(cx, bufs)
+    }
 }
 
 #[cfg(feature = "std")]
-impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for Box<T> {
-    type Error = T::Error;
+impl<T: ?Sized + AsyncBufRead + Unpin> AsyncBufRead for Box<T> {
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+        Pin::new(&mut **self.get_mut()).poll_fill_buf(cx)
+    }
 
+    #[inline]
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut **self).consume(amt)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for Box<T> {
     #[inline]
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
         Pin::new(&mut **self).poll_write(cx, buf)
     }
 
+    #[inline]
+    fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_write_vectored(cx, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
     #[inline]
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
         Pin::new(&mut **self).poll_flush(cx)
@@ -149,9 +313,17 @@ impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for Box<T> {
     }
 }
 
-impl AsyncWrite for crate::Sink {
-    type Error = Infallible;
+#[cfg(feature = "std")]
+impl<T: ?Sized + AsyncSeek + Unpin> AsyncSeek for Box<T> {
+    type Error = T::Error;
+
+    #[inline]
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>> {
+        Pin::new(&mut **self).poll_seek(cx, pos)
+    }
+}
 
+impl AsyncWrite for crate::Sink {
     #[inline]
     fn poll_write(self: Pin<&mut Self>, _: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
         Poll::Ready(Ok(buf.len()))
@@ -169,8 +341,6 @@ impl AsyncWrite for crate::Sink {
 }
 
 impl AsyncRead for crate::Empty {
-    type Error = Infallible;
-
     #[inline]
     fn poll_read(self: Pin<&mut Self>, _: &mut Context, _: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         Poll::Ready(Ok(0))
@@ -178,8 +348,6 @@ impl AsyncRead for crate::Empty {
 }
 
 impl AsyncRead for crate::Repeat {
-    type Error = Infallible;
-
     #[inline]
     fn poll_read(self: Pin<&mut Self>, _: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         for b in &mut *buf {
@@ -187,11 +355,15 @@ impl AsyncRead for crate::Repeat {
         }
         Poll::Ready(Ok(buf.len()))
     }
+
+    #[inline]
+    fn poll_read_buf(self: Pin<&mut Self>, _: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        buf.fill(self.0);
+        Poll::Ready(Ok(()))
+    }
 }
 
 impl<S: AsyncRead> AsyncRead for Take<S> {
-    type Error = S::Error;
-
     fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
         let s = unsafe { self.get_unchecked_mut() };
         let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
@@ -206,17 +378,50 @@ impl<S: AsyncRead> AsyncRead for Take<S> {
         }
         res
     }
+
+    fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+        let s = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
+
+        let res = buf.with_limit(s.limit, |buf| {
+            stream.poll_read_buf(cx, buf).map_ok(|()| buf.filled().len())
+        });
+        match res {
+            Poll::Ready(Ok(len)) => {
+                s.limit -= len;
+                Poll::Ready(Ok(()))
+            },
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Only forwards as a vectored read when `bufs` fits within the limit.
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+        if bufs.iter().map(|b| b.len()).sum::<usize>() > self.limit {
+            return match bufs.iter_mut().find(|b| !b.is_empty()) {
+                Some(buf) => self.poll_read(cx, buf),
+                None => self.poll_read(cx, &mut []),
+            }
+        }
+
+        let s = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
+        let res = stream.poll_read_vectored(cx, bufs);
+        if let Poll::Ready(Ok(len)) = &res {
+            s.limit -= len;
+        }
+        res
+    }
 }
 
 impl<S: AsyncWrite> AsyncWrite for Take<S> {
-    type Error = AllError<S::Error>;
-
     fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
         let s = unsafe { self.get_unchecked_mut() };
         let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
 
         if s.limit == 0 && !buf.is_empty() {
-            return Poll::Ready(Err(AllError::UnexpectedEof))
+            return Poll::Ready(Ok(0))
         }
 
         let buf = match buf.get(..s.limit) {
@@ -227,7 +432,30 @@ impl<S: AsyncWrite> AsyncWrite for Take<S> {
         if let Poll::Ready(Ok(len)) = &res {
             s.limit -= len;
         }
-        res.map_err(From::from)
+        res
+    }
+
+    /// Only forwards as a vectored write when `bufs` fits within the limit.
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+        if bufs.iter().map(|b| b.len()).sum::<usize>() > self.limit {
+            return match bufs.iter().find(|b| !b.is_empty()) {
+                Some(buf) => self.poll_write(cx, buf),
+                None => self.poll_write(cx, &[]),
+            }
+        }
+
+        let s = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
+        let res = stream.poll_write_vectored(cx, bufs);
+        if let Poll::Ready(Ok(len)) = &res {
+            s.limit -= len;
+        }
+        res
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
     }
 
     #[inline]
@@ -235,7 +463,7 @@ impl<S: AsyncWrite> AsyncWrite for Take<S> {
         let s = unsafe { self.get_unchecked_mut() };
         let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
 
-        stream.poll_flush(cx).map_err(From::from)
+        stream.poll_flush(cx)
     }
 
     #[inline]
@@ -243,7 +471,39 @@ impl<S: AsyncWrite> AsyncWrite for Take<S> {
         let s = unsafe { self.get_unchecked_mut() };
         let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
 
-        stream.poll_close(cx).map_err(From::from)
+        stream.poll_close(cx)
+    }
+}
+
+/// `Take` does not know its absolute position, so only relative seeks that
+/// stay within the limit are supported; anything else fails with
+/// `InvalidSeek`. Seeking forwards charges the limit and seeking backwards
+/// refunds it.
+impl<S: AsyncSeek> AsyncSeek for Take<S> where
+    S::Error: From<InvalidSeek>,
+{
+    type Error = S::Error;
+
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>> {
+        let s = unsafe { self.get_unchecked_mut() };
+        let limit = match pos {
+            SeekFrom::Current(offset) if offset >= 0 => usize::try_from(offset).ok()
+                .and_then(|n| s.limit.checked_sub(n)),
+            SeekFrom::Current(offset) => usize::try_from(offset.unsigned_abs()).ok()
+                .and_then(|n| s.limit.checked_add(n)),
+            SeekFrom::Start(_) | SeekFrom::End(_) => None,
+        };
+        let limit = match limit {
+            Some(limit) => limit,
+            None => return Poll::Ready(Err(InvalidSeek.into())),
+        };
+
+        let stream = unsafe { Pin::new_unchecked(&mut s.stream) };
+        let res = stream.poll_seek(cx, pos);
+        if let Poll::Ready(Ok(_)) = &res {
+            s.limit = limit;
+        }
+        res
     }
 }
 
@@ -254,7 +514,9 @@ mod tokio_impl {
     use core::task::{Context, Poll};
     use core::pin::Pin;
     use std::io::Error;
-    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use std::io::IoSlice;
+    use tokio::io::{AsyncRead, AsyncBufRead, AsyncWrite, AsyncSeek, ReadBuf};
+    use crate::io_slice;
 
     pub struct TokioCompat<T: ?Sized>(pub T);
 
@@ -267,34 +529,95 @@ mod tokio_impl {
         }
     }
 
-    impl<T: ?Sized + AsyncRead> super::AsyncRead for TokioCompat<T> {
+    impl<T: ?Sized> crate::ErrorType for TokioCompat<T> {
         type Error = Error;
+    }
 
+    impl<T: ?Sized + AsyncRead> super::AsyncRead for TokioCompat<T> {
         #[inline]
         fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
             let mut buf = ReadBuf::new(buf);
-            self.inner_pin().poll_read(cx, &mut buf)
-                .map_ok(|()| buf.filled().len())
+            let ptr = buf.filled().as_ptr();
+            let res = self.inner_pin().poll_read(cx, &mut buf);
+            assert!(buf.filled().as_ptr() == ptr, "the ReadBuf was replaced by the reader");
+            res.map_ok(|()| buf.filled().len())
+        }
+
+        fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut crate::ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+            let initialized = buf.initialized().len() - buf.filled().len();
+            let (res, filled, init) = {
+                let mut tbuf = ReadBuf::uninit(unsafe { buf.unfilled_mut() });
+                unsafe { tbuf.assume_init(initialized) };
+                let identity = (tbuf.filled().as_ptr(), tbuf.capacity());
+                let res = self.inner_pin().poll_read(cx, &mut tbuf);
+                // counts from a buffer the reader swapped in say nothing about ours
+                assert!((tbuf.filled().as_ptr(), tbuf.capacity()) == identity, "the ReadBuf was replaced by the reader");
+                (res, tbuf.filled().len(), tbuf.initialized().len())
+            };
+            unsafe { buf.assume_init(init) };
+            buf.advance(filled);
+            res
         }
     }
 
     impl<T: ?Sized + super::AsyncRead<Error=E>, E: Into<Error>> AsyncRead for TokioCompat<T> {
-        #[inline]
         fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf) -> Poll<Result<(), Error>> {
-            self.inner_pin().poll_read(cx, buf.initialize_unfilled())
-                .map_ok(|n| buf.advance(n))
-                .map_err(Into::into)
+            let initialized = buf.initialized().len() - buf.filled().len();
+            let (res, filled, init) = {
+                let mut ubuf = crate::ReadBuf::uninit(unsafe { buf.unfilled_mut() });
+                unsafe { ubuf.assume_init(initialized) };
+                let identity = ubuf.identity();
+                let res = self.inner_pin().poll_read_buf(cx, &mut ubuf);
+                // counts from a buffer the reader swapped in say nothing about ours
+                assert!(ubuf.identity() == identity, "the ReadBuf was replaced by the reader");
+                (res, ubuf.filled().len(), ubuf.initialized().len())
+            };
+            unsafe { buf.assume_init(init) };
+            buf.advance(filled);
+            res.map_err(Into::into)
         }
     }
 
-    impl<T: ?Sized + AsyncWrite> super::AsyncWrite for TokioCompat<T> {
-        type Error = Error;
+    impl<T: ?Sized + AsyncBufRead> super::AsyncBufRead for TokioCompat<T> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+            self.inner_pin().poll_fill_buf(cx)
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.inner_pin().consume(amt)
+        }
+    }
+
+    impl<T: ?Sized + super::AsyncBufRead<Error=E>, E: Into<Error>> AsyncBufRead for TokioCompat<T> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Error>> {
+            self.inner_pin().poll_fill_buf(cx).map_err(Into::into)
+        }
 
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.inner_pin().consume(amt)
+        }
+    }
+
+    impl<T: ?Sized + AsyncWrite> super::AsyncWrite for TokioCompat<T> {
         #[inline]
         fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
             self.inner_pin().poll_write(cx, buf)
         }
 
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[crate::IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            self.inner_pin().poll_write_vectored(cx, io_slice::to_std(bufs))
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            AsyncWrite::is_write_vectored(&self.0)
+        }
+
         #[inline]
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
             self.inner_pin().poll_flush(cx)
@@ -312,6 +635,16 @@ mod tokio_impl {
             self.inner_pin().poll_write(cx, buf).map_err(Into::into)
         }
 
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Error>> {
+            self.inner_pin().poll_write_vectored(cx, io_slice::from_std(bufs)).map_err(Into::into)
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            super::AsyncWrite::is_write_vectored(&self.0)
+        }
+
         #[inline]
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
             self.inner_pin().poll_flush(cx).map_err(Into::into)
@@ -322,17 +655,224 @@ mod tokio_impl {
             self.inner_pin().poll_close(cx).map_err(Into::into)
         }
     }
+
+    /// `TokioCompat` with the extra state needed to bridge seeking, since
+    /// tokio splits a seek into `start_seek` and `poll_complete`.
+    pub struct TokioSeekCompat<T: ?Sized> {
+        pos: Option<crate::SeekFrom>,
+        inner: TokioCompat<T>,
+    }
+
+    impl<T> TokioSeekCompat<T> {
+        pub const fn new(inner: T) -> Self {
+            Self {
+                pos: None,
+                inner: TokioCompat(inner),
+            }
+        }
+
+        pub fn into_inner(self) -> T {
+            self.inner.0
+        }
+    }
+
+    impl<T: ?Sized> TokioSeekCompat<T> {
+        #[inline]
+        pub fn inner_mut(&mut self) -> &mut T {
+            &mut self.inner.0
+        }
+
+        #[inline]
+        pub fn inner_pin(self: Pin<&mut Self>) -> Pin<&mut T> {
+            self.compat_pin().inner_pin()
+        }
+
+        #[inline]
+        fn compat_pin(self: Pin<&mut Self>) -> Pin<&mut TokioCompat<T>> {
+            unsafe {
+                self.map_unchecked_mut(|this| &mut this.inner)
+            }
+        }
+
+        #[inline]
+        fn split_pin(self: Pin<&mut Self>) -> (&mut Option<crate::SeekFrom>, Pin<&mut T>) {
+            unsafe {
+                let this = self.get_unchecked_mut();
+                (&mut this.pos, Pin::new_unchecked(&mut this.inner.0))
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsyncSeek> super::AsyncSeek for TokioSeekCompat<T> {
+        type Error = Error;
+
+        fn poll_seek(self: Pin<&mut Self>, cx: &mut Context, pos: crate::SeekFrom) -> Poll<Result<u64, Self::Error>> {
+            let (pending, mut inner) = self.split_pin();
+            if pending.is_none() {
+                // make sure no other seek is still in flight before starting ours
+                match inner.as_mut().poll_complete(cx)? {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(_) => (),
+                }
+                inner.as_mut().start_seek(pos.into())?;
+                *pending = Some(pos);
+            }
+            let res = inner.poll_complete(cx);
+            if res.is_ready() {
+                *pending = None;
+            }
+            res
+        }
+    }
+
+    impl<T: ?Sized + super::AsyncSeek<Error=E>, E: Into<Error>> AsyncSeek for TokioSeekCompat<T> {
+        fn start_seek(self: Pin<&mut Self>, pos: std::io::SeekFrom) -> Result<(), Error> {
+            let (pending, _) = self.split_pin();
+            match pending {
+                Some(_) => Err(Error::other("seek already in progress")),
+                None => {
+                    *pending = Some(pos.into());
+                    Ok(())
+                },
+            }
+        }
+
+        fn poll_complete(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<u64, Error>> {
+            let (pending, inner) = self.split_pin();
+            let pos = pending.unwrap_or(crate::SeekFrom::Current(0));
+            let res = inner.poll_seek(cx, pos).map_err(Into::into);
+            if res.is_ready() {
+                *pending = None;
+            }
+            res
+        }
+    }
+
+    impl<T: ?Sized> crate::ErrorType for TokioSeekCompat<T> {
+        type Error = <TokioCompat<T> as crate::ErrorType>::Error;
+    }
+
+    impl<T: ?Sized> super::AsyncRead for TokioSeekCompat<T> where
+        TokioCompat<T>: super::AsyncRead,
+    {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            self.compat_pin().poll_read(cx, buf)
+        }
+
+        #[inline]
+        fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut crate::ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+            self.compat_pin().poll_read_buf(cx, buf)
+        }
+    }
+
+    impl<T: ?Sized> AsyncRead for TokioSeekCompat<T> where
+        TokioCompat<T>: AsyncRead,
+    {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf) -> Poll<Result<(), Error>> {
+            self.compat_pin().poll_read(cx, buf)
+        }
+    }
+
+    impl<T: ?Sized> super::AsyncBufRead for TokioSeekCompat<T> where
+        TokioCompat<T>: super::AsyncBufRead,
+    {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+            self.compat_pin().poll_fill_buf(cx)
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            super::AsyncBufRead::consume(self.compat_pin(), amt)
+        }
+    }
+
+    impl<T: ?Sized> AsyncBufRead for TokioSeekCompat<T> where
+        TokioCompat<T>: AsyncBufRead,
+    {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Error>> {
+            self.compat_pin().poll_fill_buf(cx)
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            AsyncBufRead::consume(self.compat_pin(), amt)
+        }
+    }
+
+    impl<T: ?Sized> super::AsyncWrite for TokioSeekCompat<T> where
+        TokioCompat<T>: super::AsyncWrite,
+    {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            self.compat_pin().poll_write(cx, buf)
+        }
+
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[crate::IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            self.compat_pin().poll_write_vectored(cx, bufs)
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            super::AsyncWrite::is_write_vectored(&self.inner)
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            self.compat_pin().poll_flush(cx)
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            self.compat_pin().poll_close(cx)
+        }
+    }
+
+    impl<T: ?Sized> AsyncWrite for TokioSeekCompat<T> where
+        TokioCompat<T>: AsyncWrite,
+    {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+            self.compat_pin().poll_write(cx, buf)
+        }
+
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Error>> {
+            self.compat_pin().poll_write_vectored(cx, bufs)
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            AsyncWrite::is_write_vectored(&self.inner)
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+            self.compat_pin().poll_flush(cx)
+        }
+
+        #[inline]
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+            self.compat_pin().poll_shutdown(cx)
+        }
+    }
 }
 
 #[cfg(feature = "tokio")]
-pub use tokio_impl::TokioCompat;
+pub use tokio_impl::{TokioCompat, TokioSeekCompat};
 
 #[cfg(feature = "futures-io")]
 mod futures_impl {
     use core::task::{Context, Poll};
     use core::pin::Pin;
     use std::io::Error;
-    use futures_io::{AsyncRead, AsyncWrite};
+    use std::io::{IoSlice, IoSliceMut};
+    use futures_io::{AsyncRead, AsyncBufRead, AsyncWrite, AsyncSeek};
+    use crate::io_slice;
 
     pub struct FuturesCompat<T: ?Sized>(pub T);
 
@@ -346,13 +886,20 @@ mod futures_impl {
         }
     }
 
-    impl<T: ?Sized + AsyncRead> super::AsyncRead for FuturesCompat<T> {
+    impl<T: ?Sized> crate::ErrorType for FuturesCompat<T> {
         type Error = Error;
+    }
 
+    impl<T: ?Sized + AsyncRead> super::AsyncRead for FuturesCompat<T> {
         #[inline]
         fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
             self.inner_pin().poll_read(cx, buf)
         }
+
+        #[inline]
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [crate::IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+            self.inner_pin().poll_read_vectored(cx, io_slice::to_std_mut(bufs))
+        }
     }
 
     impl<T: ?Sized + super::AsyncRead<Error=E>, E: Into<Error>> AsyncRead for FuturesCompat<T> {
@@ -360,16 +907,48 @@ mod futures_impl {
         fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Error>> {
             self.inner_pin().poll_read(cx, buf).map_err(Into::into)
         }
+
+        #[inline]
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Error>> {
+            self.inner_pin().poll_read_vectored(cx, io_slice::from_std_mut(bufs)).map_err(Into::into)
+        }
     }
 
-    impl<T: ?Sized + AsyncWrite> super::AsyncWrite for FuturesCompat<T> {
-        type Error = Error;
+    impl<T: ?Sized + AsyncBufRead> super::AsyncBufRead for FuturesCompat<T> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+            self.inner_pin().poll_fill_buf(cx)
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.inner_pin().consume(amt)
+        }
+    }
 
+    impl<T: ?Sized + super::AsyncBufRead<Error=E>, E: Into<Error>> AsyncBufRead for FuturesCompat<T> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<&[u8], Error>> {
+            self.inner_pin().poll_fill_buf(cx).map_err(Into::into)
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.inner_pin().consume(amt)
+        }
+    }
+
+    impl<T: ?Sized + AsyncWrite> super::AsyncWrite for FuturesCompat<T> {
         #[inline]
         fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
             self.inner_pin().poll_write(cx, buf)
         }
 
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[crate::IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            self.inner_pin().poll_write_vectored(cx, io_slice::to_std(bufs))
+        }
+
         #[inline]
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
             self.inner_pin().poll_flush(cx)
@@ -387,6 +966,11 @@ mod futures_impl {
             self.inner_pin().poll_write(cx, buf).map_err(Into::into)
         }
 
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Error>> {
+            self.inner_pin().poll_write_vectored(cx, io_slice::from_std(bufs)).map_err(Into::into)
+        }
+
         #[inline]
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
             self.inner_pin().poll_flush(cx).map_err(Into::into)
@@ -397,6 +981,22 @@ mod futures_impl {
             self.inner_pin().poll_close(cx).map_err(Into::into)
         }
     }
+
+    impl<T: ?Sized + AsyncSeek> super::AsyncSeek for FuturesCompat<T> {
+        type Error = Error;
+
+        #[inline]
+        fn poll_seek(self: Pin<&mut Self>, cx: &mut Context, pos: crate::SeekFrom) -> Poll<Result<u64, Self::Error>> {
+            self.inner_pin().poll_seek(cx, pos.into())
+        }
+    }
+
+    impl<T: ?Sized + super::AsyncSeek<Error=E>, E: Into<Error>> AsyncSeek for FuturesCompat<T> {
+        #[inline]
+        fn poll_seek(self: Pin<&mut Self>, cx: &mut Context, pos: std::io::SeekFrom) -> Poll<Result<u64, Error>> {
+            self.inner_pin().poll_seek(cx, pos.into()).map_err(Into::into)
+        }
+    }
 }
 
 #[cfg(feature = "futures-io")]
@@ -417,11 +1017,24 @@ pub use copy::*;
 mod flush;
 pub use flush::*;
 
+mod seek;
+pub use seek::*;
+
+mod buf_reader;
+pub use buf_reader::*;
+
+mod buf_writer;
+pub use buf_writer::*;
+
+mod read_at;
+pub use read_at::*;
+
 pub trait AsyncReadExt: AsyncRead {
     fn read_exact<'a, 'b>(self: Pin<&'a mut Self>, buffer: &'b mut [u8]) -> AsyncReadExact<'a, 'b, Self> {
         AsyncReadExact {
             this: self,
             buffer,
+            done: 0,
         }
     }
 
@@ -432,6 +1045,24 @@ pub trait AsyncReadExt: AsyncRead {
     fn take(self, limit: usize) -> Take<Self> where Self: Sized {
         Take::new(self, limit)
     }
+
+    fn chain<B: AsyncRead>(self, next: B) -> Chain<Self, B> where Self: Sized {
+        Chain::new(self, next)
+    }
+
+    fn fuse(self) -> Fuse<Self> where Self: Sized {
+        Fuse::new(self)
+    }
+
+    #[cfg(feature = "futures-core")]
+    fn bytes(self) -> crate::Bytes<Self> where Self: Sized {
+        crate::Bytes::new(self)
+    }
+
+    #[cfg(feature = "futures-core")]
+    fn chars(self) -> crate::Chars<Self> where Self: Sized {
+        crate::Chars::new(self)
+    }
 }
 
 impl<T: ?Sized + AsyncRead> AsyncReadExt for T { }
@@ -441,6 +1072,15 @@ pub trait AsyncWriteExt: AsyncWrite {
         AsyncWriteAll {
             this: self,
             buffer,
+            done: 0,
+        }
+    }
+
+    fn write_all_vectored<'a, 'b, 'c>(self: Pin<&'a mut Self>, bufs: &'b mut [IoSlice<'c>]) -> AsyncWriteAllVectored<'a, 'b, 'c, Self> {
+        AsyncWriteAllVectored {
+            this: self,
+            bufs,
+            done: 0,
         }
     }
 
@@ -464,12 +1104,29 @@ pub trait AsyncSynchronousExt: AsyncSynchronous {
         AsyncReadWriteAll {
             this: self,
             buffer,
+            done: 0,
         }
     }
 }
 
 impl<T: ?Sized + AsyncSynchronous> AsyncSynchronousExt for T { }
 
+pub trait AsyncSeekExt: AsyncSeek {
+    fn seek<'a>(self: Pin<&'a mut Self>, pos: SeekFrom) -> AsyncSeekTo<'a, Self> {
+        AsyncSeekTo::new(self, pos)
+    }
+
+    fn rewind<'a>(self: Pin<&'a mut Self>) -> AsyncSeekTo<'a, Self> {
+        AsyncSeekTo::new(self, SeekFrom::Start(0))
+    }
+
+    fn stream_position<'a>(self: Pin<&'a mut Self>) -> AsyncSeekTo<'a, Self> {
+        AsyncSeekTo::new(self, SeekFrom::Current(0))
+    }
+}
+
+impl<T: ?Sized + AsyncSeek> AsyncSeekExt for T { }
+
 trait BufferSlice {
     fn len(&self) -> usize;
     unsafe fn resize_from(&mut self, count: usize);
@@ -521,14 +1178,53 @@ fn all_poll<B: BufferSlice>(res: Poll<usize>, cx: &mut Context, buffer: &mut B)
     }
 }
 
-#[inline]
-fn all_poll_write<B: BufferSlice>(res: Poll<usize>, cx: &mut Context, buffer: &mut B) -> Poll<()> {
-    match all_poll(res, cx, buffer) {
-        Poll::Pending => Poll::Pending,
-        Poll::Ready(res) => match res {
-            #[cfg(debug_assertions)]
-            Err(()) => panic!("Invalid write length"),
-            _ => Poll::Ready(()),
+/// Turns an `Interrupted` error into a retry.
+pub(crate) fn retry_interrupted<T, E: IoError>(res: Poll<Result<T, E>>, cx: &mut Context) -> Poll<Result<T, E>> {
+    match res {
+        Poll::Ready(Err(ref e)) if e.kind() == ErrorKind::Interrupted => {
+            cx.waker().wake_by_ref();
+            Poll::Pending
         },
+        res => res,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::task::{Context, Poll, Waker};
+    use core::pin::Pin;
+    use crate::{AllError, AsyncRead, AsyncSeek, Cursor, InvalidSeek, SeekFrom, Take};
+
+    fn poll_seek<S: AsyncSeek + Unpin>(stream: &mut S, pos: SeekFrom) -> Poll<Result<u64, S::Error>> {
+        let mut cx = Context::from_waker(Waker::noop());
+        Pin::new(stream).poll_seek(&mut cx, pos)
+    }
+
+    #[test]
+    fn take_seek_charges_and_refunds_the_limit() {
+        let mut take = Take::new(Cursor::new(*b"abcdef"), 4);
+        assert_eq!(poll_seek(&mut take, SeekFrom::Current(3)), Poll::Ready(Ok(3)));
+        assert_eq!(take.limit(), 1);
+        assert_eq!(poll_seek(&mut take, SeekFrom::Current(-2)), Poll::Ready(Ok(1)));
+        assert_eq!(take.limit(), 3);
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut buf = [0u8; 8];
+        assert_eq!(Pin::new(&mut take).poll_read(&mut cx, &mut buf), Poll::Ready(Ok(3)));
+        assert_eq!(&buf[..3], b"bcd");
+    }
+
+    #[test]
+    fn take_seek_rejects_what_it_cannot_account_for() {
+        let mut take = Take::new(Cursor::new(*b"abcdef"), 2);
+        assert_eq!(poll_seek(&mut take, SeekFrom::Current(3)), Poll::Ready(Err(AllError::Io(InvalidSeek))));
+        assert_eq!(poll_seek(&mut take, SeekFrom::Start(0)), Poll::Ready(Err(AllError::Io(InvalidSeek))));
+        assert_eq!(poll_seek(&mut take, SeekFrom::End(0)), Poll::Ready(Err(AllError::Io(InvalidSeek))));
+        assert_eq!(take.limit(), 2);
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut buf = [0u8; 8];
+        assert_eq!(Pin::new(&mut take).poll_read(&mut cx, &mut buf), Poll::Ready(Ok(2)));
+        assert_eq!(&buf[..2], b"ab");
     }
 }
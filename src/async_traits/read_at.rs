@@ -0,0 +1,175 @@
+use core::future::Future;
+use core::task::{Context, Poll};
+use core::ops::DerefMut;
+use core::pin::Pin;
+use crate::{AllError, ErrorType};
+use super::retry_interrupted;
+
+pub trait AsyncReadAt: ErrorType {
+    fn poll_read_at(self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>>;
+}
+
+pub trait AsyncWriteAt: ErrorType {
+    fn poll_write_at(self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &[u8]) -> Poll<Result<usize, Self::Error>>;
+
+    fn poll_flush_at(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: ?Sized + AsyncReadAt + Unpin> AsyncReadAt for &'_ mut T {
+    #[inline]
+    fn poll_read_at(mut self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_read_at(cx, offset, buf)
+    }
+}
+
+impl<T: ?Sized + AsyncWriteAt + Unpin> AsyncWriteAt for &'_ mut T {
+    #[inline]
+    fn poll_write_at(mut self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_write_at(cx, offset, buf)
+    }
+
+    #[inline]
+    fn poll_flush_at(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush_at(cx)
+    }
+}
+
+impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncReadAt> AsyncReadAt for Pin<P> {
+    #[inline]
+    fn poll_read_at(self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+        self.get_mut().as_mut().poll_read_at(cx, offset, buf)
+    }
+}
+
+impl<P: DerefMut<Target=T> + Unpin, T: ?Sized + AsyncWriteAt> AsyncWriteAt for Pin<P> {
+    #[inline]
+    fn poll_write_at(self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+        self.get_mut().as_mut().poll_write_at(cx, offset, buf)
+    }
+
+    #[inline]
+    fn poll_flush_at(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().as_mut().poll_flush_at(cx)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized + AsyncReadAt + Unpin> AsyncReadAt for Box<T> {
+    #[inline]
+    fn poll_read_at(mut self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_read_at(cx, offset, buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized + AsyncWriteAt + Unpin> AsyncWriteAt for Box<T> {
+    #[inline]
+    fn poll_write_at(mut self: Pin<&mut Self>, cx: &mut Context, offset: u64, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+        Pin::new(&mut **self).poll_write_at(cx, offset, buf)
+    }
+
+    #[inline]
+    fn poll_flush_at(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush_at(cx)
+    }
+}
+
+impl AsyncReadAt for [u8] {
+    #[inline]
+    fn poll_read_at(self: Pin<&mut Self>, _: &mut Context, offset: u64, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+        let this = self.get_mut();
+        let offset = crate::slice_offset(this.len(), offset);
+        let mut rem = unsafe { this.get_unchecked(offset..) };
+        Poll::Ready(Ok(crate::slice_read(&mut rem, buf)))
+    }
+}
+
+impl AsyncReadAt for &'_ [u8] {
+    #[inline]
+    fn poll_read_at(self: Pin<&mut Self>, _: &mut Context, offset: u64, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+        let this = self.get_mut();
+        let offset = crate::slice_offset(this.len(), offset);
+        let mut rem = unsafe { this.get_unchecked(offset..) };
+        Poll::Ready(Ok(crate::slice_read(&mut rem, buf)))
+    }
+}
+
+impl AsyncWriteAt for [u8] {
+    #[inline]
+    fn poll_write_at(self: Pin<&mut Self>, _: &mut Context, offset: u64, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+        let this = self.get_mut();
+        let offset = crate::slice_offset(this.len(), offset);
+        let mut rem = unsafe { this.get_unchecked_mut(offset..) };
+        Poll::Ready(crate::slice_write(&mut rem, buf))
+    }
+}
+
+pub trait AsyncReadAtExt: AsyncReadAt {
+    fn read_exact_at<'a, 'b>(self: Pin<&'a mut Self>, offset: u64, buffer: &'b mut [u8]) -> AsyncReadExactAt<'a, 'b, Self> {
+        AsyncReadExactAt {
+            this: self,
+            offset,
+            buffer,
+            done: 0,
+        }
+    }
+}
+
+impl<T: ?Sized + AsyncReadAt> AsyncReadAtExt for T { }
+
+pub trait AsyncWriteAtExt: AsyncWriteAt {
+    fn write_all_at<'a, 'b>(self: Pin<&'a mut Self>, offset: u64, buffer: &'b [u8]) -> AsyncWriteAllAt<'a, 'b, Self> {
+        AsyncWriteAllAt {
+            this: self,
+            offset,
+            buffer,
+            done: 0,
+        }
+    }
+}
+
+impl<T: ?Sized + AsyncWriteAt> AsyncWriteAtExt for T { }
+
+pub struct AsyncReadExactAt<'a, 'b, T: ?Sized> {
+    this: Pin<&'a mut T>,
+    offset: u64,
+    buffer: &'b mut [u8],
+    done: usize,
+}
+
+impl<'a, 'b, T: ?Sized + AsyncReadAt> Future for AsyncReadExactAt<'a, 'b, T> {
+    type Output = Result<(), AllError<T::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let s = self.as_mut().get_mut();
+        let res = retry_interrupted(s.this.as_mut().poll_read_at(cx, s.offset, s.buffer), cx).map_err(|e| AllError::from_io(e, s.done))?;
+        if let Poll::Ready(count) = res {
+            s.offset = s.offset.saturating_add(count as u64);
+            s.done = s.done.saturating_add(count);
+        }
+        super::all_poll(res, cx, &mut s.buffer).map_err(|_| AllError::UnexpectedEof)
+    }
+}
+
+pub struct AsyncWriteAllAt<'a, 'b, T: ?Sized> {
+    this: Pin<&'a mut T>,
+    offset: u64,
+    buffer: &'b [u8],
+    done: usize,
+}
+
+impl<'a, 'b, T: ?Sized + AsyncWriteAt> Future for AsyncWriteAllAt<'a, 'b, T> {
+    type Output = Result<(), AllError<T::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let s = self.as_mut().get_mut();
+        let res = retry_interrupted(s.this.as_mut().poll_write_at(cx, s.offset, s.buffer), cx).map_err(|e| AllError::from_io(e, s.done))?;
+        if let Poll::Ready(count) = res {
+            s.offset = s.offset.saturating_add(count as u64);
+            s.done = s.done.saturating_add(count);
+        }
+        super::all_poll(res, cx, &mut s.buffer).map_err(|_| AllError::WriteZero)
+    }
+}
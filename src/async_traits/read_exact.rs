@@ -2,11 +2,12 @@ use core::future::Future;
 use core::task::{Context, Poll};
 use core::pin::Pin;
 use crate::AllError;
-use super::all_poll;
+use super::{all_poll, retry_interrupted};
 
 pub struct AsyncReadExact<'a, 'b, T: ?Sized> {
     pub(crate) this: Pin<&'a mut T>,
     pub(crate) buffer: &'b mut [u8],
+    pub(crate) done: usize,
 }
 
 impl<'a, 'b, T: ?Sized + super::AsyncRead> Future for AsyncReadExact<'a, 'b, T> {
@@ -15,7 +16,10 @@ impl<'a, 'b, T: ?Sized + super::AsyncRead> Future for AsyncReadExact<'a, 'b, T>
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         let s = self.as_mut().get_mut();
         let this = s.this.as_mut();
-        let res = this.poll_read(cx, s.buffer)?;
+        let res = retry_interrupted(this.poll_read(cx, s.buffer), cx).map_err(|e| AllError::from_io(e, s.done))?;
+        if let Poll::Ready(count) = res {
+            s.done = s.done.saturating_add(count);
+        }
         all_poll(res, cx, &mut self.buffer).map_err(|_| AllError::UnexpectedEof)
     }
 }
@@ -0,0 +1,28 @@
+use core::future::Future;
+use core::task::{Context, Poll};
+use core::pin::Pin;
+use crate::SeekFrom;
+
+pub struct AsyncSeekTo<'a, T: ?Sized> {
+    this: Pin<&'a mut T>,
+    pos: SeekFrom,
+}
+
+impl<'a, T: ?Sized> AsyncSeekTo<'a, T> {
+    pub fn new(this: Pin<&'a mut T>, pos: SeekFrom) -> Self {
+        Self {
+            this,
+            pos,
+        }
+    }
+}
+
+impl<'a, T: ?Sized + super::AsyncSeek> Future for AsyncSeekTo<'a, T> {
+    type Output = Result<u64, T::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let s = self.as_mut().get_mut();
+        let pos = s.pos;
+        s.this.as_mut().poll_seek(cx, pos)
+    }
+}
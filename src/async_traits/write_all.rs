@@ -1,20 +1,51 @@
 use core::future::Future;
 use core::task::{Context, Poll};
 use core::pin::Pin;
-use super::all_poll_write;
+use crate::{AllError, IoSlice};
+use super::{all_poll, retry_interrupted};
 
 pub struct AsyncWriteAll<'a, 'b, T: ?Sized> {
     pub(crate) this: Pin<&'a mut T>,
     pub(crate) buffer: &'b [u8],
+    pub(crate) done: usize,
 }
 
 impl<'a, 'b, T: ?Sized + super::AsyncWrite> Future for AsyncWriteAll<'a, 'b, T> {
-    type Output = Result<(), T::Error>;
+    type Output = Result<(), AllError<T::Error>>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         let s = self.as_mut().get_mut();
         let this = s.this.as_mut();
-        let res = this.poll_write(cx, s.buffer)?;
-        all_poll_write(res, cx, &mut self.buffer).map(Ok)
+        let res = retry_interrupted(this.poll_write(cx, s.buffer), cx).map_err(|e| AllError::from_io(e, s.done))?;
+        if let Poll::Ready(count) = res {
+            s.done = s.done.saturating_add(count);
+        }
+        all_poll(res, cx, &mut self.buffer).map_err(|_| AllError::WriteZero)
+    }
+}
+
+pub struct AsyncWriteAllVectored<'a, 'b, 'c, T: ?Sized> {
+    pub(crate) this: Pin<&'a mut T>,
+    pub(crate) bufs: &'b mut [IoSlice<'c>],
+    pub(crate) done: usize,
+}
+
+impl<'a, 'b, 'c, T: ?Sized + super::AsyncWrite> Future for AsyncWriteAllVectored<'a, 'b, 'c, T> {
+    type Output = Result<(), AllError<T::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let s = self.as_mut().get_mut();
+        IoSlice::advance_slices(&mut s.bufs, 0);
+        while !s.bufs.is_empty() {
+            match retry_interrupted(s.this.as_mut().poll_write_vectored(cx, s.bufs), cx).map_err(|e| AllError::from_io(e, s.done))? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(0) => return Poll::Ready(Err(AllError::WriteZero)),
+                Poll::Ready(n) => {
+                    IoSlice::advance_slices(&mut s.bufs, n);
+                    s.done = s.done.saturating_add(n);
+                },
+            }
+        }
+        Poll::Ready(Ok(()))
     }
 }
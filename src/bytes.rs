@@ -0,0 +1,362 @@
+use core::{fmt, str};
+
+/// Reads one byte at a time; wrap unbuffered streams in a buffered reader first.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Bytes<R> {
+    inner: R,
+}
+
+impl<R> Bytes<R> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Decodes UTF-8 one byte at a time.
+///
+/// An invalid sequence is reported once and skipped. If it was cut short by
+/// a byte that cannot continue it, decoding resumes at that byte.
+#[derive(Debug, Copy, Clone)]
+pub struct Chars<R> {
+    inner: R,
+    decoder: Utf8Decoder,
+}
+
+impl<R> Chars<R> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            decoder: Utf8Decoder::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Any partially decoded character is discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CharsError<E> {
+    /// The stream contained a byte sequence that is not valid UTF-8.
+    InvalidUtf8,
+    /// The stream ended partway through a character.
+    Incomplete,
+    Io(E),
+}
+
+impl<E> From<E> for CharsError<E> {
+    #[inline]
+    fn from(e: E) -> Self {
+        CharsError::Io(e)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CharsError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharsError::InvalidUtf8 => f.write_str("invalid UTF-8 sequence"),
+            CharsError::Incomplete => f.write_str("stream ended within a UTF-8 sequence"),
+            CharsError::Io(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl<E: crate::IoError> crate::IoError for CharsError<E> {
+    fn kind(&self) -> crate::ErrorKind {
+        match self {
+            CharsError::InvalidUtf8 => crate::ErrorKind::InvalidData,
+            CharsError::Incomplete => crate::ErrorKind::UnexpectedEof,
+            CharsError::Io(e) => e.kind(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for CharsError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CharsError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Utf8Decoder {
+    buf: [u8; 4],
+    filled: usize,
+    held: Option<u8>,
+}
+
+impl Utf8Decoder {
+    const fn new() -> Self {
+        Self {
+            buf: [0u8; 4],
+            filled: 0,
+            held: None,
+        }
+    }
+
+    fn push<E>(&mut self, byte: u8) -> Option<Result<char, CharsError<E>>> {
+        self.buf[self.filled] = byte;
+        self.filled += 1;
+
+        let width = match self.buf[0] {
+            0x00..=0x7f => 1,
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => 0,
+        };
+        if width == 0 {
+            self.filled = 0;
+            return Some(Err(CharsError::InvalidUtf8))
+        }
+        if self.filled > 1 && byte & 0xc0 != 0x80 {
+            // the byte may well start the next character
+            self.filled = 0;
+            self.held = Some(byte);
+            return Some(Err(CharsError::InvalidUtf8))
+        }
+        if self.filled < width {
+            return None
+        }
+
+        self.filled = 0;
+        Some(match str::from_utf8(&self.buf[..width]) {
+            Ok(s) => s.chars().next().ok_or(CharsError::InvalidUtf8),
+            Err(_) => Err(CharsError::InvalidUtf8),
+        })
+    }
+
+    /// Decodes the byte held back by `push`, if any.
+    fn resume<E>(&mut self) -> Option<Result<char, CharsError<E>>> {
+        self.held.take().and_then(|byte| self.push(byte))
+    }
+
+    fn finish<E>(&mut self) -> Option<Result<char, CharsError<E>>> {
+        match self.filled {
+            0 => None,
+            _ => {
+                self.filled = 0;
+                Some(Err(CharsError::Incomplete))
+            },
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use crate::{IoError, ErrorKind};
+    use super::{Bytes, Chars, CharsError};
+
+    impl<R: crate::Read> Iterator for Bytes<R> {
+        type Item = Result<u8, R::Error>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let mut byte = 0u8;
+            loop {
+                match self.inner.read(core::slice::from_mut(&mut byte)) {
+                    Ok(0) => break None,
+                    Ok(_) => break Some(Ok(byte)),
+                    Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                    Err(e) => break Some(Err(e)),
+                }
+            }
+        }
+    }
+
+    impl<R: crate::Read> Iterator for Chars<R> {
+        type Item = Result<char, CharsError<R::Error>>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                if let Some(res) = self.decoder.resume() {
+                    break Some(res)
+                }
+                let mut byte = 0u8;
+                match self.inner.read(core::slice::from_mut(&mut byte)) {
+                    Ok(0) => break self.decoder.finish(),
+                    Ok(_) => if let Some(res) = self.decoder.push(byte) {
+                        break Some(res)
+                    },
+                    Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                    Err(e) => break Some(Err(CharsError::Io(e))),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "async", feature = "futures-core"))]
+mod stream_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use futures_core::Stream;
+    use crate::async_traits::retry_interrupted;
+    use super::{Bytes, Chars, CharsError};
+
+    impl<R: crate::AsyncRead> Stream for Bytes<R> {
+        type Item = Result<u8, R::Error>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            let mut byte = 0u8;
+            retry_interrupted(inner.poll_read(cx, core::slice::from_mut(&mut byte)), cx).map(|res| match res {
+                Ok(0) => None,
+                Ok(_) => Some(Ok(byte)),
+                Err(e) => Some(Err(e)),
+            })
+        }
+    }
+
+    impl<R: crate::AsyncRead> Stream for Chars<R> {
+        type Item = Result<char, CharsError<R::Error>>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let mut inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            loop {
+                if let Some(res) = this.decoder.resume() {
+                    break Poll::Ready(Some(res))
+                }
+                let mut byte = 0u8;
+                match retry_interrupted(inner.as_mut().poll_read(cx, core::slice::from_mut(&mut byte)), cx) {
+                    Poll::Pending => break Poll::Pending,
+                    Poll::Ready(Ok(0)) => break Poll::Ready(this.decoder.finish()),
+                    Poll::Ready(Ok(_)) => if let Some(res) = this.decoder.push(byte) {
+                        break Poll::Ready(Some(res))
+                    },
+                    Poll::Ready(Err(e)) => break Poll::Ready(Some(Err(CharsError::Io(e)))),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sync")]
+    mod sync_tests {
+        use crate::ReadExt;
+        use super::super::CharsError;
+
+        #[test]
+        fn multibyte_characters_decode() {
+            let mut chars = "h€𝄞".as_bytes().chars();
+            assert_eq!(chars.next(), Some(Ok('h')));
+            assert_eq!(chars.next(), Some(Ok('€')));
+            assert_eq!(chars.next(), Some(Ok('𝄞')));
+            assert_eq!(chars.next(), None);
+        }
+
+        #[test]
+        fn sequence_cut_short_by_eof() {
+            let mut chars = (&b"a\xe2\x82"[..]).chars();
+            assert_eq!(chars.next(), Some(Ok('a')));
+            assert_eq!(chars.next(), Some(Err(CharsError::Incomplete)));
+            assert_eq!(chars.next(), None);
+        }
+
+        #[test]
+        fn sequence_cut_short_by_a_new_character() {
+            let mut chars = (&b"\xe2\x82a\xe2\xe2\x82\xac"[..]).chars();
+            assert_eq!(chars.next(), Some(Err(CharsError::InvalidUtf8)));
+            assert_eq!(chars.next(), Some(Ok('a')));
+            assert_eq!(chars.next(), Some(Err(CharsError::InvalidUtf8)));
+            assert_eq!(chars.next(), Some(Ok('€')));
+            assert_eq!(chars.next(), None);
+        }
+
+        #[test]
+        fn invalid_sequences_are_skipped() {
+            let mut chars = (&b"\xffb\xed\xa0\x80c"[..]).chars();
+            assert_eq!(chars.next(), Some(Err(CharsError::InvalidUtf8)));
+            assert_eq!(chars.next(), Some(Ok('b')));
+            assert_eq!(chars.next(), Some(Err(CharsError::InvalidUtf8)));
+            assert_eq!(chars.next(), Some(Ok('c')));
+            assert_eq!(chars.next(), None);
+        }
+    }
+
+    #[cfg(all(feature = "async", feature = "futures-core"))]
+    mod stream_tests {
+        use core::task::{Context, Poll, Waker};
+        use core::pin::Pin;
+        use futures_core::Stream;
+        use crate::AsyncReadExt;
+
+        /// Hands out one byte per read, with a `Pending` before each.
+        struct Trickle<'a> {
+            data: &'a [u8],
+            ready: bool,
+        }
+
+        impl crate::ErrorType for Trickle<'_> {
+            type Error = core::convert::Infallible;
+        }
+
+        impl crate::AsyncRead for Trickle<'_> {
+            fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+                if !self.ready {
+                    self.ready = true;
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending
+                }
+                self.ready = false;
+                let len = buf.len().min(self.data.len()).min(1);
+                buf[..len].copy_from_slice(&self.data[..len]);
+                self.data = &self.data[len..];
+                Poll::Ready(Ok(len))
+            }
+        }
+
+        #[test]
+        fn sequence_split_across_pending_reads() {
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut chars = Trickle { data: "€!".as_bytes(), ready: false }.chars();
+            let mut decoded = [None; 2];
+            let mut n = 0;
+            for _ in 0..32 {
+                match Pin::new(&mut chars).poll_next(&mut cx) {
+                    Poll::Pending => (),
+                    Poll::Ready(Some(c)) => {
+                        decoded[n] = Some(c);
+                        n += 1;
+                    },
+                    Poll::Ready(None) => break,
+                }
+            }
+            assert_eq!(decoded, [Some(Ok('€')), Some(Ok('!'))]);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::ErrorType;
+
+/// Reads all of `first`, then all of `second`.
+///
+/// Errors from `second` are converted into the error type of `first`.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    done_first: bool,
+}
+
+impl<A, B> Chain<A, B> {
+    pub const fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            done_first: false,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+/// Reads each reader produced by an iterator to completion in turn.
+pub struct Concat<I: Iterator> {
+    current: Option<I::Item>,
+    rest: I,
+}
+
+impl<I: Iterator> Concat<I> {
+    pub fn new<T: IntoIterator<IntoIter=I>>(readers: T) -> Self {
+        let mut rest = readers.into_iter();
+        Self {
+            current: rest.next(),
+            rest,
+        }
+    }
+
+    #[inline]
+    pub fn current(&self) -> Option<&I::Item> {
+        self.current.as_ref()
+    }
+
+    #[inline]
+    pub fn current_mut(&mut self) -> Option<&mut I::Item> {
+        self.current.as_mut()
+    }
+
+    /// Returns the remaining readers, starting with the current one.
+    pub fn into_inner(self) -> (Option<I::Item>, I) {
+        (self.current, self.rest)
+    }
+}
+
+impl<A: ErrorType, B: ErrorType> ErrorType for Chain<A, B> where
+    A::Error: From<B::Error>,
+{
+    type Error = A::Error;
+}
+
+impl<I: Iterator> ErrorType for Concat<I> where
+    I::Item: ErrorType,
+{
+    type Error = <I::Item as ErrorType>::Error;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use super::{Chain, Concat};
+
+    impl<A: crate::Read, B: crate::Read> crate::Read for Chain<A, B> where
+        A::Error: From<B::Error>,
+    {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if !self.done_first {
+                match self.first.read(buf)? {
+                    0 if !buf.is_empty() => self.done_first = true,
+                    len => return Ok(len),
+                }
+            }
+            self.second.read(buf).map_err(From::from)
+        }
+    }
+
+    impl<I: Iterator> crate::Read for Concat<I> where
+        I::Item: crate::Read,
+    {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            while let Some(reader) = &mut self.current {
+                match reader.read(buf)? {
+                    0 if !buf.is_empty() => self.current = self.rest.next(),
+                    len => return Ok(len),
+                }
+            }
+            Ok(0)
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use super::{Chain, Concat};
+
+    impl<A: crate::AsyncRead, B: crate::AsyncRead> crate::AsyncRead for Chain<A, B> where
+        A::Error: From<B::Error>,
+    {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            if !this.done_first {
+                let first = unsafe { Pin::new_unchecked(&mut this.first) };
+                match first.poll_read(cx, buf)? {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(0) if !buf.is_empty() => this.done_first = true,
+                    Poll::Ready(len) => return Poll::Ready(Ok(len)),
+                }
+            }
+            let second = unsafe { Pin::new_unchecked(&mut this.second) };
+            second.poll_read(cx, buf).map_err(From::from)
+        }
+    }
+
+    impl<I: Iterator> crate::AsyncRead for Concat<I> where
+        I::Item: crate::AsyncRead,
+    {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            // `current` is pinned in place; it is only ever dropped and replaced, never moved
+            let this = unsafe { self.get_unchecked_mut() };
+            while let Some(reader) = &mut this.current {
+                let reader = unsafe { Pin::new_unchecked(reader) };
+                match reader.poll_read(cx, buf)? {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(0) if !buf.is_empty() => this.current = this.rest.next(),
+                    Poll::Ready(len) => return Poll::Ready(Ok(len)),
+                }
+            }
+            Poll::Ready(Ok(0))
+        }
+    }
+}
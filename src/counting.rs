@@ -0,0 +1,202 @@
+/// Counts the bytes passing through a stream.
+///
+/// A synchronous transfer counts towards both `read` and `written`. The
+/// counters saturate at `u64::MAX`.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Counting<S> {
+    stream: S,
+    read: u64,
+    written: u64,
+}
+
+impl<S> Counting<S> {
+    pub const fn new(stream: S) -> Self {
+        Self {
+            stream,
+            read: 0,
+            written: 0,
+        }
+    }
+
+    #[inline]
+    pub fn bytes_read(&self) -> u64 {
+        self.read
+    }
+
+    #[inline]
+    pub fn bytes_written(&self) -> u64 {
+        self.written
+    }
+
+    pub fn reset(&mut self) {
+        self.read = 0;
+        self.written = 0;
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: crate::ErrorType> crate::ErrorType for Counting<S> {
+    type Error = S::Error;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use crate::{IoSlice, IoSliceMut, ReadBuf};
+    use super::Counting;
+
+    impl<S: crate::Read> crate::Read for Counting<S> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let len = self.stream.read(buf)?;
+            self.read = self.read.saturating_add(len as u64);
+            Ok(len)
+        }
+
+        fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+            let (identity, filled) = (buf.identity(), buf.filled().len());
+            self.stream.read_buf(buf)?;
+            assert!(buf.identity() == identity, "the ReadBuf was replaced by the reader");
+            self.read = self.read.saturating_add(buf.filled().len().saturating_sub(filled) as u64);
+            Ok(())
+        }
+
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Self::Error> {
+            let len = self.stream.read_vectored(bufs)?;
+            self.read = self.read.saturating_add(len as u64);
+            Ok(len)
+        }
+    }
+
+    impl<S: crate::Write> crate::Write for Counting<S> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let len = self.stream.write(buf)?;
+            self.written = self.written.saturating_add(len as u64);
+            Ok(len)
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Self::Error> {
+            let len = self.stream.write_vectored(bufs)?;
+            self.written = self.written.saturating_add(len as u64);
+            Ok(len)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.stream.flush()
+        }
+
+        #[inline]
+        fn close(&mut self) -> Result<(), Self::Error> {
+            self.stream.close()
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use crate::{IoSlice, IoSliceMut, ReadBuf};
+    use super::Counting;
+
+    impl<S: crate::AsyncRead> crate::AsyncRead for Counting<S> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_read(cx, buf);
+            if let Poll::Ready(Ok(len)) = &res {
+                this.read = this.read.saturating_add(*len as u64);
+            }
+            res
+        }
+
+        fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let (identity, filled) = (buf.identity(), buf.filled().len());
+            let res = stream.poll_read_buf(cx, buf);
+            assert!(buf.identity() == identity, "the ReadBuf was replaced by the reader");
+            if let Poll::Ready(Ok(())) = &res {
+                this.read = this.read.saturating_add(buf.filled().len().saturating_sub(filled) as u64);
+            }
+            res
+        }
+
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_read_vectored(cx, bufs);
+            if let Poll::Ready(Ok(len)) = &res {
+                this.read = this.read.saturating_add(*len as u64);
+            }
+            res
+        }
+    }
+
+    impl<S: crate::AsyncWrite> crate::AsyncWrite for Counting<S> {
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_write(cx, buf);
+            if let Poll::Ready(Ok(len)) = &res {
+                this.written = this.written.saturating_add(*len as u64);
+            }
+            res
+        }
+
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_write_vectored(cx, bufs);
+            if let Poll::Ready(Ok(len)) = &res {
+                this.written = this.written.saturating_add(*len as u64);
+            }
+            res
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            self.stream.is_write_vectored()
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            stream.poll_flush(cx)
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            stream.poll_close(cx)
+        }
+    }
+
+    impl<S: crate::AsyncSynchronous> crate::AsyncSynchronous for Counting<S> {
+        fn poll_read_write(self: Pin<&mut Self>, cx: &mut Context, buffer: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_read_write(cx, buffer);
+            if let Poll::Ready(Ok(len)) = &res {
+                this.read = this.read.saturating_add(*len as u64);
+                this.written = this.written.saturating_add(*len as u64);
+            }
+            res
+        }
+    }
+}
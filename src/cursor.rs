@@ -0,0 +1,201 @@
+use core::cmp;
+use crate::{AllError, SeekFrom, InvalidSeek};
+
+/// Wraps an in-memory buffer with a stream position.
+///
+/// Writes never grow the underlying buffer, and fail with
+/// `AllError::UnexpectedEof` once its end is reached. Seeks before the start
+/// fail with `AllError::Io(InvalidSeek)`.
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Cursor<T> {
+    inner: T,
+    pos: u64,
+}
+
+impl<T> Cursor<T> {
+    pub const fn new(inner: T) -> Self {
+        Self {
+            inner,
+            pos: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    #[inline]
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl<T: AsRef<[u8]>> Cursor<T> {
+    #[inline]
+    fn offset(&self) -> usize {
+        cmp::min(self.pos, self.inner.as_ref().len() as u64) as usize
+    }
+
+    pub fn remaining_slice(&self) -> &[u8] {
+        let offset = self.offset();
+        unsafe {
+            self.inner.as_ref().get_unchecked(offset..)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.inner.as_ref().len() as u64
+    }
+
+    pub(crate) fn seek_to(&mut self, pos: SeekFrom) -> Result<u64, AllError<InvalidSeek>> {
+        self.pos = crate::seek_position(pos, self.pos, Some(self.inner.as_ref().len() as u64))?;
+        Ok(self.pos)
+    }
+
+    pub(crate) fn read_slice(&mut self, buf: &mut [u8]) -> usize {
+        let len = crate::slice_read(&mut self.remaining_slice(), buf);
+        self.pos += len as u64;
+        len
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> Cursor<T> {
+    pub(crate) fn write_slice(&mut self, buf: &[u8]) -> Result<usize, AllError<InvalidSeek>> {
+        let offset = self.offset();
+        let mut rem = unsafe {
+            self.inner.as_mut().get_unchecked_mut(offset..)
+        };
+        let len = match crate::slice_write(&mut rem, buf) {
+            Ok(len) => len,
+            Err(_) => return Err(AllError::UnexpectedEof),
+        };
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+/// Reads never fail; writes fail once the end of the buffer is reached.
+/// Seeks share this error type.
+impl<T> crate::ErrorType for Cursor<T> {
+    type Error = AllError<InvalidSeek>;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use crate::{AllError, SeekFrom, InvalidSeek};
+    use super::Cursor;
+
+    impl<T: AsRef<[u8]>> crate::Read for Cursor<T> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(self.read_slice(buf))
+        }
+    }
+
+    impl<T: AsRef<[u8]>> crate::BufRead for Cursor<T> {
+        #[inline]
+        fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+            Ok(self.remaining_slice())
+        }
+
+        #[inline]
+        fn consume(&mut self, amt: usize) {
+            self.pos = self.pos.saturating_add(amt as u64);
+        }
+    }
+
+    impl<T: AsRef<[u8]> + AsMut<[u8]>> crate::Write for Cursor<T> {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.write_slice(buf)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl<T: AsRef<[u8]>> crate::Seek for Cursor<T> {
+        type Error = AllError<InvalidSeek>;
+
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            self.seek_to(pos)
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use crate::{AllError, SeekFrom, InvalidSeek};
+    use super::Cursor;
+
+    impl<T: AsRef<[u8]>> crate::AsyncRead for Cursor<T> {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, _: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            Poll::Ready(Ok(this.read_slice(buf)))
+        }
+    }
+
+    impl<T: AsRef<[u8]>> crate::AsyncBufRead for Cursor<T> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<&[u8], Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            Poll::Ready(Ok(this.remaining_slice()))
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            let this = unsafe { self.get_unchecked_mut() };
+            this.pos = this.pos.saturating_add(amt as u64);
+        }
+    }
+
+    impl<T: AsRef<[u8]> + AsMut<[u8]>> crate::AsyncWrite for Cursor<T> {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, _: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            Poll::Ready(this.write_slice(buf))
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<T: AsRef<[u8]>> crate::AsyncSeek for Cursor<T> {
+        type Error = AllError<InvalidSeek>;
+
+        #[inline]
+        fn poll_seek(self: Pin<&mut Self>, _: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            Poll::Ready(this.seek_to(pos))
+        }
+    }
+}
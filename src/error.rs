@@ -0,0 +1,207 @@
+use core::convert::Infallible;
+use core::ops::DerefMut;
+use core::pin::Pin;
+use crate::{AllError, InvalidSeek, Sink, Empty, Repeat, Take};
+
+/// A general category of I/O error, mirroring `std::io::ErrorKind`.
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    NotFound,
+    PermissionDenied,
+    ConnectionRefused,
+    ConnectionReset,
+    ConnectionAborted,
+    NotConnected,
+    AddrInUse,
+    AddrNotAvailable,
+    BrokenPipe,
+    AlreadyExists,
+    /// The operation needs to block to complete, but was asked not to.
+    WouldBlock,
+    InvalidInput,
+    InvalidData,
+    TimedOut,
+    WriteZero,
+    /// The operation was interrupted and can be retried.
+    Interrupted,
+    Unsupported,
+    UnexpectedEof,
+    OutOfMemory,
+    Other,
+}
+
+/// Classifies a stream error.
+///
+/// The provided helpers such as `read_exact` and `write_all` retry on
+/// `ErrorKind::Interrupted`, report `ErrorKind::WouldBlock` as
+/// `AllError::WouldBlock` with the number of bytes already transferred, and
+/// return any other error to the caller.
+pub trait IoError {
+    /// Defaults to `ErrorKind::Other`.
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// The error type shared by every stream trait a type implements.
+pub trait ErrorType {
+    type Error: IoError;
+}
+
+impl<T: ?Sized + ErrorType> ErrorType for &'_ mut T {
+    type Error = T::Error;
+}
+
+impl<P: DerefMut<Target=T>, T: ?Sized + ErrorType> ErrorType for Pin<P> {
+    type Error = T::Error;
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized + ErrorType> ErrorType for Box<T> {
+    type Error = T::Error;
+}
+
+impl ErrorType for &'_ [u8] {
+    type Error = Infallible;
+}
+
+/// Also covers writes through `&mut [u8]`, which fail once the slice is full.
+impl ErrorType for [u8] {
+    type Error = AllError<Infallible>;
+}
+
+impl ErrorType for Sink {
+    type Error = Infallible;
+}
+
+impl ErrorType for Empty {
+    type Error = Infallible;
+}
+
+impl ErrorType for Repeat {
+    type Error = Infallible;
+}
+
+/// Reads and writes share the inner stream's error. Writes past the limit
+/// return `Ok(0)`, which `write_all` reports as `AllError::WriteZero`.
+impl<S: ErrorType> ErrorType for Take<S> {
+    type Error = S::Error;
+}
+
+impl<T: ?Sized + IoError> IoError for &'_ T {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        (**self).kind()
+    }
+}
+
+impl IoError for ErrorKind {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl IoError for Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self { }
+    }
+}
+
+impl<E: IoError> IoError for AllError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            AllError::UnexpectedEof => ErrorKind::UnexpectedEof,
+            AllError::WriteZero => ErrorKind::WriteZero,
+            AllError::WouldBlock(_) => ErrorKind::WouldBlock,
+            AllError::Io(e) => e.kind(),
+        }
+    }
+}
+
+impl<E> AllError<E> {
+    /// Wraps a stream error, pulling out `WouldBlock` along with the number
+    /// of bytes already transferred.
+    pub(crate) fn from_io<F: IoError>(e: F, done: usize) -> Self where E: From<F> {
+        match e.kind() {
+            ErrorKind::WouldBlock => AllError::WouldBlock(done),
+            _ => AllError::Io(e.into()),
+        }
+    }
+}
+
+impl IoError for InvalidSeek {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::InvalidInput
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_impl {
+    use std::io;
+    use super::{ErrorKind, IoError};
+
+    impl IoError for io::Error {
+        #[inline]
+        fn kind(&self) -> ErrorKind {
+            self.kind().into()
+        }
+    }
+
+    impl From<io::ErrorKind> for ErrorKind {
+        fn from(kind: io::ErrorKind) -> Self {
+            match kind {
+                io::ErrorKind::NotFound => ErrorKind::NotFound,
+                io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+                io::ErrorKind::ConnectionRefused => ErrorKind::ConnectionRefused,
+                io::ErrorKind::ConnectionReset => ErrorKind::ConnectionReset,
+                io::ErrorKind::ConnectionAborted => ErrorKind::ConnectionAborted,
+                io::ErrorKind::NotConnected => ErrorKind::NotConnected,
+                io::ErrorKind::AddrInUse => ErrorKind::AddrInUse,
+                io::ErrorKind::AddrNotAvailable => ErrorKind::AddrNotAvailable,
+                io::ErrorKind::BrokenPipe => ErrorKind::BrokenPipe,
+                io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+                io::ErrorKind::WouldBlock => ErrorKind::WouldBlock,
+                io::ErrorKind::InvalidInput => ErrorKind::InvalidInput,
+                io::ErrorKind::InvalidData => ErrorKind::InvalidData,
+                io::ErrorKind::TimedOut => ErrorKind::TimedOut,
+                io::ErrorKind::WriteZero => ErrorKind::WriteZero,
+                io::ErrorKind::Interrupted => ErrorKind::Interrupted,
+                io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+                io::ErrorKind::UnexpectedEof => ErrorKind::UnexpectedEof,
+                io::ErrorKind::OutOfMemory => ErrorKind::OutOfMemory,
+                _ => ErrorKind::Other,
+            }
+        }
+    }
+
+    impl From<ErrorKind> for io::ErrorKind {
+        fn from(kind: ErrorKind) -> Self {
+            match kind {
+                ErrorKind::NotFound => io::ErrorKind::NotFound,
+                ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+                ErrorKind::ConnectionRefused => io::ErrorKind::ConnectionRefused,
+                ErrorKind::ConnectionReset => io::ErrorKind::ConnectionReset,
+                ErrorKind::ConnectionAborted => io::ErrorKind::ConnectionAborted,
+                ErrorKind::NotConnected => io::ErrorKind::NotConnected,
+                ErrorKind::AddrInUse => io::ErrorKind::AddrInUse,
+                ErrorKind::AddrNotAvailable => io::ErrorKind::AddrNotAvailable,
+                ErrorKind::BrokenPipe => io::ErrorKind::BrokenPipe,
+                ErrorKind::AlreadyExists => io::ErrorKind::AlreadyExists,
+                ErrorKind::WouldBlock => io::ErrorKind::WouldBlock,
+                ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
+                ErrorKind::InvalidData => io::ErrorKind::InvalidData,
+                ErrorKind::TimedOut => io::ErrorKind::TimedOut,
+                ErrorKind::WriteZero => io::ErrorKind::WriteZero,
+                ErrorKind::Interrupted => io::ErrorKind::Interrupted,
+                ErrorKind::Unsupported => io::ErrorKind::Unsupported,
+                ErrorKind::UnexpectedEof => io::ErrorKind::UnexpectedEof,
+                ErrorKind::OutOfMemory => io::ErrorKind::OutOfMemory,
+                ErrorKind::Other => io::ErrorKind::Other,
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{ErrorType, ErrorKind, IoError};
+
+/// Keeps returning EOF once the inner reader has returned it.
+///
+/// Readers built with `Fuse::latch_errors` also replay the first error on
+/// every later call, except `Interrupted` and `WouldBlock`, which are worth
+/// retrying.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Fuse<R, E = ()> {
+    inner: R,
+    eof: bool,
+    error: E,
+}
+
+impl<R> Fuse<R> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            eof: false,
+            error: (),
+        }
+    }
+}
+
+impl<R, E> Fuse<R, Option<E>> {
+    pub const fn latch_errors(inner: R) -> Self {
+        Self {
+            inner,
+            eof: false,
+            error: None,
+        }
+    }
+
+    #[inline]
+    pub fn error(&self) -> Option<&E> {
+        self.error.as_ref()
+    }
+}
+
+impl<R, E: IoError + Clone> Fuse<R, Option<E>> {
+    #[inline]
+    fn latch_error(&mut self, e: &E) {
+        match e.kind() {
+            ErrorKind::Interrupted | ErrorKind::WouldBlock => (),
+            _ => self.error = Some(e.clone()),
+        }
+    }
+}
+
+impl<R, E> Fuse<R, E> {
+    #[inline]
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    #[inline]
+    fn latch_len(&mut self, len: usize, buf_len: usize) -> usize {
+        if len == 0 && buf_len != 0 {
+            self.eof = true;
+        }
+        len
+    }
+}
+
+impl<R: ErrorType> ErrorType for Fuse<R> {
+    type Error = R::Error;
+}
+
+impl<R: ErrorType> ErrorType for Fuse<R, Option<R::Error>> {
+    type Error = R::Error;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use super::Fuse;
+
+    impl<R: crate::Read> crate::Read for Fuse<R> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if self.eof {
+                return Ok(0)
+            }
+            let len = self.inner.read(buf)?;
+            Ok(self.latch_len(len, buf.len()))
+        }
+    }
+
+    impl<R: crate::Read> crate::Read for Fuse<R, Option<R::Error>> where
+        R::Error: Clone,
+    {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if let Some(e) = &self.error {
+                return Err(e.clone())
+            }
+            if self.eof {
+                return Ok(0)
+            }
+            match self.inner.read(buf) {
+                Ok(len) => Ok(self.latch_len(len, buf.len())),
+                Err(e) => {
+                    self.latch_error(&e);
+                    Err(e)
+                },
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use super::Fuse;
+
+    impl<R: crate::AsyncRead> crate::AsyncRead for Fuse<R> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            if this.eof {
+                return Poll::Ready(Ok(0))
+            }
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            inner.poll_read(cx, buf).map_ok(|len| this.latch_len(len, buf.len()))
+        }
+    }
+
+    impl<R: crate::AsyncRead> crate::AsyncRead for Fuse<R, Option<R::Error>> where
+        R::Error: Clone,
+    {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            if let Some(e) = &this.error {
+                return Poll::Ready(Err(e.clone()))
+            }
+            if this.eof {
+                return Poll::Ready(Ok(0))
+            }
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            match inner.poll_read(cx, buf) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(Ok(len)) => Poll::Ready(Ok(this.latch_len(len, buf.len()))),
+                Poll::Ready(Err(e)) => {
+                    this.latch_error(&e);
+                    Poll::Ready(Err(e))
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErrorKind;
+    use super::Fuse;
+
+    /// Plays back a fixed sequence of results, one per call, then EOF.
+    struct Script<'a> {
+        steps: &'a [Result<&'a [u8], ErrorKind>],
+    }
+
+    impl<'a> Script<'a> {
+        fn next(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+            match self.steps.split_first() {
+                None => Ok(0),
+                Some((step, rest)) => {
+                    self.steps = rest;
+                    let data = (*step)?;
+                    buf[..data.len()].copy_from_slice(data);
+                    Ok(data.len())
+                },
+            }
+        }
+    }
+
+    impl crate::ErrorType for Script<'_> {
+        type Error = ErrorKind;
+    }
+
+    const FLAKY: &[Result<&[u8], ErrorKind>] = &[Ok(b"ab"), Ok(b""), Ok(b"cd")];
+    const FAILING: &[Result<&[u8], ErrorKind>] = &[Ok(b"ab"), Err(ErrorKind::TimedOut), Ok(b"cd")];
+    const INTERRUPTED: &[Result<&[u8], ErrorKind>] = &[Err(ErrorKind::Interrupted), Err(ErrorKind::WouldBlock), Ok(b"cd")];
+
+    #[cfg(feature = "sync")]
+    mod sync_tests {
+        use crate::{Read, ReadExt};
+        use super::{Fuse, Script, ErrorKind, FLAKY, FAILING, INTERRUPTED};
+
+        impl Read for Script<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                self.next(buf)
+            }
+        }
+
+        #[test]
+        fn empty_stays_eof() {
+            let mut fuse = crate::Empty.fuse();
+            let mut buf = [0u8; 4];
+            assert_eq!(fuse.read(&mut buf), Ok(0));
+            assert!(fuse.is_eof());
+            assert_eq!(fuse.read(&mut buf), Ok(0));
+        }
+
+        #[test]
+        fn eof_is_latched() {
+            let mut fuse = Fuse::new(Script { steps: FLAKY });
+            let mut buf = [0u8; 4];
+            assert_eq!(fuse.read(&mut buf), Ok(2));
+            assert_eq!(&buf[..2], b"ab");
+            assert_eq!(fuse.read(&mut buf), Ok(0));
+            assert_eq!(fuse.read(&mut buf), Ok(0));
+            assert_eq!(fuse.get_ref().steps.len(), 1);
+        }
+
+        #[test]
+        fn empty_buffer_does_not_latch() {
+            let mut fuse = Fuse::new(crate::Empty);
+            assert_eq!(fuse.read(&mut []), Ok(0));
+            assert!(!fuse.is_eof());
+        }
+
+        #[test]
+        fn errors_pass_through() {
+            let mut fuse = Fuse::new(Script { steps: FAILING });
+            let mut buf = [0u8; 4];
+            assert_eq!(fuse.read(&mut buf), Ok(2));
+            assert_eq!(fuse.read(&mut buf), Err(ErrorKind::TimedOut));
+            assert_eq!(fuse.read(&mut buf), Ok(2));
+        }
+
+        #[test]
+        fn errors_are_latched() {
+            let mut fuse = Fuse::latch_errors(Script { steps: FAILING });
+            let mut buf = [0u8; 4];
+            assert_eq!(fuse.read(&mut buf), Ok(2));
+            assert_eq!(fuse.read(&mut buf), Err(ErrorKind::TimedOut));
+            assert_eq!(fuse.read(&mut buf), Err(ErrorKind::TimedOut));
+            assert_eq!(fuse.error(), Some(&ErrorKind::TimedOut));
+        }
+
+        #[test]
+        fn retryable_errors_are_not_latched() {
+            let mut fuse = Fuse::latch_errors(Script { steps: INTERRUPTED });
+            let mut buf = [0u8; 4];
+            assert_eq!(fuse.read(&mut buf), Err(ErrorKind::Interrupted));
+            assert_eq!(fuse.read(&mut buf), Err(ErrorKind::WouldBlock));
+            assert_eq!(fuse.error(), None);
+            assert_eq!(fuse.read(&mut buf), Ok(2));
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod async_tests {
+        use core::task::{Context, Poll, Waker};
+        use core::pin::Pin;
+        use crate::{AsyncRead, AsyncReadExt};
+        use super::{Fuse, Script, ErrorKind, FLAKY, FAILING, INTERRUPTED};
+
+        impl AsyncRead for Script<'_> {
+            fn poll_read(self: Pin<&mut Self>, _: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+                Poll::Ready(self.get_mut().next(buf))
+            }
+        }
+
+        fn poll_read<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> Poll<Result<usize, R::Error>> {
+            let mut cx = Context::from_waker(Waker::noop());
+            Pin::new(reader).poll_read(&mut cx, buf)
+        }
+
+        #[test]
+        fn empty_stays_eof() {
+            let mut fuse = AsyncReadExt::fuse(crate::Empty);
+            let mut buf = [0u8; 4];
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(0)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(0)));
+        }
+
+        #[test]
+        fn eof_is_latched() {
+            let mut fuse = Fuse::new(Script { steps: FLAKY });
+            let mut buf = [0u8; 4];
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(2)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(0)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(0)));
+        }
+
+        #[test]
+        fn errors_are_latched() {
+            let mut fuse = Fuse::latch_errors(Script { steps: FAILING });
+            let mut buf = [0u8; 4];
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(2)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Err(ErrorKind::TimedOut)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Err(ErrorKind::TimedOut)));
+        }
+
+        #[test]
+        fn retryable_errors_are_not_latched() {
+            let mut fuse = Fuse::latch_errors(Script { steps: INTERRUPTED });
+            let mut buf = [0u8; 4];
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Err(ErrorKind::Interrupted)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Err(ErrorKind::WouldBlock)));
+            assert_eq!(poll_read(&mut fuse, &mut buf), Poll::Ready(Ok(2)));
+        }
+    }
+}
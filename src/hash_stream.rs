@@ -16,14 +16,28 @@ impl<H, S> HashStream<H, S> {
     }
 }
 
+impl<H, S: crate::ErrorType> crate::ErrorType for HashStream<H, S> {
+    type Error = S::Error;
+}
+
+#[cfg(feature = "async")]
+fn hash_prefix<'a, H: core::hash::Hasher, I: Iterator<Item=&'a [u8]>>(hasher: &mut H, bufs: I, mut len: usize) {
+    for buf in bufs {
+        if len == 0 {
+            break
+        }
+        let buf = buf.get(..len).unwrap_or(buf);
+        hasher.write(buf);
+        len -= buf.len();
+    }
+}
+
 #[cfg(feature = "sync")]
 mod sync_impl {
     use core::hash::Hasher;
     use super::HashStream;
 
     impl<H: Hasher, S: crate::Read> crate::Read for HashStream<H, S> {
-        type Error = S::Error;
-
         fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
             let res = self.stream.read(buf);
             if let Ok(len) = &res {
@@ -34,8 +48,6 @@ mod sync_impl {
     }
 
     impl<H: Hasher, S: crate::Write> crate::Write for HashStream<H, S> {
-        type Error = S::Error;
-
         fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
             let res = self.stream.write(buf);
             if let Ok(len) = &res {
@@ -48,6 +60,11 @@ mod sync_impl {
         fn flush(&mut self) -> Result<(), Self::Error> {
             self.stream.flush()
         }
+
+        #[inline]
+        fn close(&mut self) -> Result<(), Self::Error> {
+            self.stream.close()
+        }
     }
 }
 
@@ -56,11 +73,10 @@ mod async_impl {
     use core::task::{Context, Poll};
     use core::pin::Pin;
     use core::hash::Hasher;
-    use super::HashStream;
+    use crate::{IoSlice, IoSliceMut, ReadBuf};
+    use super::{HashStream, hash_prefix};
 
     impl<H: Hasher, S: crate::AsyncRead> crate::AsyncRead for HashStream<H, S> {
-        type Error = S::Error;
-
         fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
             let this = unsafe { self.get_unchecked_mut() };
             let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
@@ -71,11 +87,30 @@ mod async_impl {
 
             res
         }
+
+        fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let filled = buf.filled().len();
+            let res = stream.poll_read_buf(cx, buf);
+            if let Poll::Ready(Ok(())) = &res {
+                this.hasher.write(unsafe { buf.filled().get_unchecked(filled..) });
+            }
+            res
+        }
+
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_read_vectored(cx, bufs);
+            if let Poll::Ready(Ok(len)) = &res {
+                hash_prefix(&mut this.hasher, bufs.iter().map(|b| &b[..]), *len);
+            }
+            res
+        }
     }
 
     impl<H: Hasher, S: crate::AsyncWrite> crate::AsyncWrite for HashStream<H, S> {
-        type Error = S::Error;
-
         fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
             let this = unsafe { self.get_unchecked_mut() };
             let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
@@ -86,6 +121,21 @@ mod async_impl {
             res
         }
 
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            let res = stream.poll_write_vectored(cx, bufs);
+            if let Poll::Ready(Ok(len)) = &res {
+                hash_prefix(&mut this.hasher, bufs.iter().map(|b| &b[..]), *len);
+            }
+            res
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            self.stream.is_write_vectored()
+        }
+
         #[inline]
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
             let this = unsafe { self.get_unchecked_mut() };
@@ -97,7 +147,7 @@ mod async_impl {
         fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
             let this = unsafe { self.get_unchecked_mut() };
             let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
-            stream.poll_flush(cx)
+            stream.poll_close(cx)
         }
     }
 }
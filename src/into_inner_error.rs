@@ -0,0 +1,54 @@
+use core::fmt;
+
+/// Returned by the buffered writers' `into_inner` when the final flush fails,
+/// handing the writer back along with the error.
+#[derive(Debug)]
+pub struct IntoInnerError<W, E> {
+    writer: W,
+    error: E,
+}
+
+impl<W, E> IntoInnerError<W, E> {
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn new(writer: W, error: E) -> Self {
+        Self {
+            writer,
+            error,
+        }
+    }
+
+    #[inline]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    #[inline]
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn into_parts(self) -> (E, W) {
+        (self.error, self.writer)
+    }
+}
+
+impl<W, E: fmt::Display> fmt::Display for IntoInnerError<W, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: fmt::Debug, E: std::error::Error + 'static> std::error::Error for IntoInnerError<W, E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,264 @@
+use core::ops::{Deref, DerefMut};
+use core::{fmt, mem};
+
+/// A buffer for vectored writes.
+///
+/// With the `std` feature this shares its layout with `std::io::IoSlice`, so
+/// vectored I/O can be forwarded to `std` without copying.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct IoSlice<'a> {
+    #[cfg(feature = "std")]
+    inner: std::io::IoSlice<'a>,
+    #[cfg(not(feature = "std"))]
+    inner: &'a [u8],
+}
+
+/// A buffer for vectored reads.
+///
+/// With the `std` feature this shares its layout with `std::io::IoSliceMut`.
+#[repr(transparent)]
+pub struct IoSliceMut<'a> {
+    #[cfg(feature = "std")]
+    inner: std::io::IoSliceMut<'a>,
+    #[cfg(not(feature = "std"))]
+    inner: &'a mut [u8],
+}
+
+impl<'a> IoSlice<'a> {
+    #[inline]
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            inner: std::io::IoSlice::new(buf),
+            #[cfg(not(feature = "std"))]
+            inner: buf,
+        }
+    }
+
+    /// Panics if `n` is larger than the slice.
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        #[cfg(feature = "std")] {
+            self.inner.advance(n)
+        }
+        #[cfg(not(feature = "std"))] {
+            self.inner = &self.inner[n..]
+        }
+    }
+
+    /// Skips `n` bytes across `bufs`, removing any slices that are fully consumed.
+    ///
+    /// Skipping past the end leaves `bufs` empty, so a stream that claims to
+    /// have written more than it was given cannot cause a panic.
+    pub fn advance_slices(bufs: &mut &mut [IoSlice<'a>], n: usize) {
+        let (remove, left) = advance_count(bufs.iter().map(|b| b.len()), n);
+        *bufs = &mut mem::take(bufs)[remove..];
+        if let Some(buf) = bufs.first_mut() {
+            buf.advance(left)
+        }
+    }
+}
+
+impl<'a> IoSliceMut<'a> {
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            inner: std::io::IoSliceMut::new(buf),
+            #[cfg(not(feature = "std"))]
+            inner: buf,
+        }
+    }
+
+    /// Panics if `n` is larger than the slice.
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        #[cfg(feature = "std")] {
+            self.inner.advance(n)
+        }
+        #[cfg(not(feature = "std"))] {
+            self.inner = &mut mem::take(&mut self.inner)[n..]
+        }
+    }
+
+    /// Skips `n` bytes across `bufs`, removing any slices that are fully consumed.
+    ///
+    /// Skipping past the end leaves `bufs` empty, so a stream that claims to
+    /// have written more than it was given cannot cause a panic.
+    pub fn advance_slices(bufs: &mut &mut [IoSliceMut<'a>], n: usize) {
+        let (remove, left) = advance_count(bufs.iter().map(|b| b.len()), n);
+        *bufs = &mut mem::take(bufs)[remove..];
+        if let Some(buf) = bufs.first_mut() {
+            buf.advance(left)
+        }
+    }
+}
+
+fn advance_count<I: Iterator<Item=usize>>(lens: I, n: usize) -> (usize, usize) {
+    let mut remove = 0usize;
+    let mut left = n;
+    for len in lens {
+        match left.checked_sub(len) {
+            Some(rem) => {
+                left = rem;
+                remove += 1;
+            },
+            None => break,
+        }
+    }
+    (remove, left)
+}
+
+impl<'a> Deref for IoSlice<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        #[cfg(feature = "std")] {
+            &self.inner
+        }
+        #[cfg(not(feature = "std"))] {
+            self.inner
+        }
+    }
+}
+
+impl<'a> Deref for IoSliceMut<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        #[cfg(feature = "std")] {
+            &self.inner
+        }
+        #[cfg(not(feature = "std"))] {
+            self.inner
+        }
+    }
+}
+
+impl<'a> DerefMut for IoSliceMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        #[cfg(feature = "std")] {
+            &mut self.inner
+        }
+        #[cfg(not(feature = "std"))] {
+            &mut *self.inner
+        }
+    }
+}
+
+impl<'a> fmt::Debug for IoSlice<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a> fmt::Debug for IoSliceMut<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_impl {
+    use super::{IoSlice, IoSliceMut};
+
+    impl<'a> From<IoSlice<'a>> for std::io::IoSlice<'a> {
+        #[inline]
+        fn from(buf: IoSlice<'a>) -> Self {
+            buf.inner
+        }
+    }
+
+    impl<'a> From<std::io::IoSlice<'a>> for IoSlice<'a> {
+        #[inline]
+        fn from(inner: std::io::IoSlice<'a>) -> Self {
+            Self { inner }
+        }
+    }
+
+    impl<'a> From<IoSliceMut<'a>> for std::io::IoSliceMut<'a> {
+        #[inline]
+        fn from(buf: IoSliceMut<'a>) -> Self {
+            buf.inner
+        }
+    }
+
+    impl<'a> From<std::io::IoSliceMut<'a>> for IoSliceMut<'a> {
+        #[inline]
+        fn from(inner: std::io::IoSliceMut<'a>) -> Self {
+            Self { inner }
+        }
+    }
+
+    // these casts rely on the `repr(transparent)` wrappers above
+
+    #[cfg(any(feature = "sync", feature = "tokio", feature = "futures-io"))]
+    #[inline]
+    pub(crate) fn to_std<'a, 'b>(bufs: &'b [IoSlice<'a>]) -> &'b [std::io::IoSlice<'a>] {
+        unsafe { &*(bufs as *const [IoSlice<'a>] as *const [std::io::IoSlice<'a>]) }
+    }
+
+    #[cfg(any(feature = "sync", feature = "tokio", feature = "futures-io"))]
+    #[inline]
+    pub(crate) fn from_std<'a, 'b>(bufs: &'b [std::io::IoSlice<'a>]) -> &'b [IoSlice<'a>] {
+        unsafe { &*(bufs as *const [std::io::IoSlice<'a>] as *const [IoSlice<'a>]) }
+    }
+
+    #[cfg(any(feature = "sync", feature = "tokio", feature = "futures-io"))]
+    #[inline]
+    pub(crate) fn to_std_mut<'a, 'b>(bufs: &'b mut [IoSliceMut<'a>]) -> &'b mut [std::io::IoSliceMut<'a>] {
+        unsafe { &mut *(bufs as *mut [IoSliceMut<'a>] as *mut [std::io::IoSliceMut<'a>]) }
+    }
+
+    #[cfg(any(feature = "sync", feature = "tokio", feature = "futures-io"))]
+    #[inline]
+    pub(crate) fn from_std_mut<'a, 'b>(bufs: &'b mut [std::io::IoSliceMut<'a>]) -> &'b mut [IoSliceMut<'a>] {
+        unsafe { &mut *(bufs as *mut [std::io::IoSliceMut<'a>] as *mut [IoSliceMut<'a>]) }
+    }
+}
+
+#[cfg(all(feature = "std", any(feature = "sync", feature = "tokio", feature = "futures-io")))]
+pub(crate) use std_impl::{to_std, from_std, to_std_mut, from_std_mut};
+
+#[cfg(test)]
+mod tests {
+    use super::{IoSlice, IoSliceMut};
+
+    #[test]
+    fn advance_slices_drops_consumed_slices() {
+        let mut bufs = [IoSlice::new(b"ab"), IoSlice::new(b""), IoSlice::new(b"cde")];
+        let mut bufs = &mut bufs[..];
+        IoSlice::advance_slices(&mut bufs, 0);
+        assert_eq!(bufs.len(), 3);
+        IoSlice::advance_slices(&mut bufs, 3);
+        assert_eq!(bufs.len(), 1);
+        assert_eq!(&*bufs[0], b"de");
+        IoSlice::advance_slices(&mut bufs, 2);
+        assert!(bufs.is_empty());
+    }
+
+    #[test]
+    fn advance_slices_clamps_past_the_end() {
+        let mut bufs = [IoSlice::new(b"ab"), IoSlice::new(b"c")];
+        let mut bufs = &mut bufs[..];
+        IoSlice::advance_slices(&mut bufs, 10);
+        assert!(bufs.is_empty());
+    }
+
+    #[test]
+    fn advance_slices_mut_splits_a_slice() {
+        let (mut a, mut b) = (*b"ab", *b"cd");
+        let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+        let mut bufs = &mut bufs[..];
+        IoSliceMut::advance_slices(&mut bufs, 3);
+        assert_eq!(bufs.len(), 1);
+        bufs[0][0] = b'x';
+        IoSliceMut::advance_slices(&mut bufs, 5);
+        assert!(bufs.is_empty());
+        assert_eq!(&b, b"cx");
+    }
+}
@@ -14,12 +14,55 @@ mod async_traits;
 #[cfg(feature = "async")]
 pub use async_traits::*;
 
+mod error;
+pub use error::*;
+
+mod cursor;
+pub use cursor::*;
+
+mod offset_stream;
+pub use offset_stream::*;
+
+mod io_slice;
+pub use io_slice::*;
+
+mod read_buf;
+pub use read_buf::*;
+
+mod into_inner_error;
+pub use into_inner_error::*;
+
+mod chain;
+pub use chain::*;
+
+mod tee;
+pub use tee::*;
+
+mod map_err;
+pub use map_err::*;
+
+mod counting;
+pub use counting::*;
+
+mod fuse;
+pub use fuse::*;
+
+mod peekable;
+pub use peekable::*;
+
+#[cfg(any(feature = "sync", all(feature = "async", feature = "futures-core")))]
+mod bytes;
+#[cfg(any(feature = "sync", all(feature = "async", feature = "futures-core")))]
+pub use bytes::*;
+
 #[cfg(feature = "hash-stream")]
 mod hash_stream;
 #[cfg(feature = "hash-stream")]
 pub use hash_stream::*;
 
 pub mod prelude {
+    pub use super::ErrorTypeExt;
+
     #[cfg(feature = "sync")]
     pub use super::sync_traits::prelude::*;
 
@@ -73,6 +116,10 @@ impl<S> Take<S> {
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 pub enum AllError<E> {
     UnexpectedEof,
+    /// The stream accepted zero bytes of a non-empty write.
+    WriteZero,
+    /// The stream would block after this many bytes were transferred.
+    WouldBlock(usize),
     Io(E),
 }
 
@@ -87,6 +134,8 @@ impl<E: fmt::Display> fmt::Display for AllError<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             AllError::UnexpectedEof => f.write_str("Unexpected EOF"),
+            AllError::WriteZero => f.write_str("Failed to write whole buffer"),
+            AllError::WouldBlock(_) => f.write_str("Operation would block"),
             AllError::Io(e) => fmt::Display::fmt(e, f),
         }
     }
@@ -97,11 +146,73 @@ impl<E: ufmt::uDisplay> ufmt::uDisplay for AllError<E> {
     fn fmt<W: ?Sized + ufmt::uWrite>(&self, f: &mut ufmt::Formatter<W>) -> Result<(), W::Error> {
         match self {
             AllError::UnexpectedEof => f.write_str("Unexpected EOF"),
+            AllError::WriteZero => f.write_str("Failed to write whole buffer"),
+            AllError::WouldBlock(_) => f.write_str("Operation would block"),
             AllError::Io(e) => ufmt::uDisplay::fmt(e, f),
         }
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum SeekFrom {
+    Start(u64),
+    End(i64),
+    Current(i64),
+}
+
+#[cfg(feature = "std")]
+impl From<SeekFrom> for std::io::SeekFrom {
+    #[inline]
+    fn from(pos: SeekFrom) -> Self {
+        match pos {
+            SeekFrom::Start(n) => std::io::SeekFrom::Start(n),
+            SeekFrom::End(n) => std::io::SeekFrom::End(n),
+            SeekFrom::Current(n) => std::io::SeekFrom::Current(n),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::SeekFrom> for SeekFrom {
+    #[inline]
+    fn from(pos: std::io::SeekFrom) -> Self {
+        match pos {
+            std::io::SeekFrom::Start(n) => SeekFrom::Start(n),
+            std::io::SeekFrom::End(n) => SeekFrom::End(n),
+            std::io::SeekFrom::Current(n) => SeekFrom::Current(n),
+        }
+    }
+}
+
+/// A seek to a negative or overflowing position.
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct InvalidSeek;
+
+impl fmt::Display for InvalidSeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Invalid seek position")
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for InvalidSeek {
+    fn fmt<W: ?Sized + ufmt::uWrite>(&self, f: &mut ufmt::Formatter<W>) -> Result<(), W::Error> {
+        f.write_str("Invalid seek position")
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for InvalidSeek { }
+
+#[cfg(feature = "std")]
+impl From<InvalidSeek> for std::io::Error {
+    fn from(e: InvalidSeek) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[cfg(feature = "ufmt")]
 pub struct uWriter<W: ?Sized> {
@@ -121,6 +232,18 @@ impl<W> uWriter<W> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<E: Into<std::io::Error>> From<AllError<E>> for std::io::Error {
+    fn from(e: AllError<E>) -> Self {
+        match e {
+            AllError::UnexpectedEof => std::io::ErrorKind::UnexpectedEof.into(),
+            AllError::WriteZero => std::io::ErrorKind::WriteZero.into(),
+            AllError::WouldBlock(_) => std::io::ErrorKind::WouldBlock.into(),
+            AllError::Io(e) => e.into(),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<E: StdError + 'static> StdError for AllError<E> {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
@@ -131,6 +254,11 @@ impl<E: StdError + 'static> StdError for AllError<E> {
     }
 }
 
+#[inline]
+fn slice_offset(len: usize, offset: u64) -> usize {
+    cmp::min(offset, len as u64) as usize
+}
+
 fn slice_read(this: &mut &[u8], buf: &mut [u8]) -> usize {
     let len = cmp::min(buf.len(), this.len());
     unsafe {
@@ -156,3 +284,18 @@ fn slice_write(this: &mut &mut [u8], buf: &[u8]) -> Result<usize, AllError<conve
         Ok(len)
     }
 }
+
+/// Resolves `pos` against the current position and, if it is known, the
+/// length of the stream.
+fn seek_position(pos: SeekFrom, current: u64, len: Option<u64>) -> Result<u64, InvalidSeek> {
+    let (base, offset) = match pos {
+        SeekFrom::Start(n) => return Ok(n),
+        SeekFrom::End(n) => (len.ok_or(InvalidSeek)?, n),
+        SeekFrom::Current(n) => (current, n),
+    };
+    let pos = match offset >= 0 {
+        true => base.checked_add(offset as u64),
+        false => base.checked_sub(offset.unsigned_abs()),
+    };
+    pos.ok_or(InvalidSeek)
+}
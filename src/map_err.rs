@@ -0,0 +1,338 @@
+use core::marker::PhantomData;
+use core::fmt;
+use crate::{ErrorType, IoError};
+
+/// Maps the errors of a stream through `f`.
+#[derive(Copy, Clone)]
+pub struct MapErr<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> MapErr<S, F> {
+    pub const fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for MapErr<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapErr")
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+/// Converts the errors of a stream into `E`.
+pub struct ErrInto<S, E> {
+    stream: S,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<S, E> ErrInto<S, E> {
+    pub const fn new(stream: S) -> Self {
+        Self {
+            stream,
+            _error: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Clone, E> Clone for ErrInto<S, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.stream.clone())
+    }
+}
+
+impl<S: Copy, E> Copy for ErrInto<S, E> { }
+
+impl<S: fmt::Debug, E> fmt::Debug for ErrInto<S, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ErrInto")
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+impl<S: ErrorType, F: FnMut(S::Error) -> E, E: IoError> ErrorType for MapErr<S, F> {
+    type Error = E;
+}
+
+impl<S, E: IoError> ErrorType for ErrInto<S, E> {
+    type Error = E;
+}
+
+/// Error adapters for any stream, whichever of the I/O traits it implements.
+pub trait ErrorTypeExt: ErrorType {
+    fn map_err<F: FnMut(Self::Error) -> E, E>(self, f: F) -> MapErr<Self, F> where Self: Sized {
+        MapErr::new(self, f)
+    }
+
+    fn err_into<E>(self) -> ErrInto<Self, E> where Self: Sized, Self::Error: Into<E> {
+        ErrInto::new(self)
+    }
+}
+
+impl<T: ?Sized + ErrorType> ErrorTypeExt for T { }
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use crate::{IoSlice, IoSliceMut, ReadBuf};
+    use super::{MapErr, ErrInto};
+
+    impl<S: crate::Read, F: FnMut(S::Error) -> E, E: crate::IoError> crate::Read for MapErr<S, F> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.stream.read(buf).map_err(&mut self.f)
+        }
+
+        #[inline]
+        fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+            self.stream.read_buf(buf).map_err(&mut self.f)
+        }
+
+        #[inline]
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Self::Error> {
+            self.stream.read_vectored(bufs).map_err(&mut self.f)
+        }
+    }
+
+    impl<S: crate::Write, F: FnMut(S::Error) -> E, E: crate::IoError> crate::Write for MapErr<S, F> {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.stream.write(buf).map_err(&mut self.f)
+        }
+
+        #[inline]
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Self::Error> {
+            self.stream.write_vectored(bufs).map_err(&mut self.f)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.stream.flush().map_err(&mut self.f)
+        }
+
+        #[inline]
+        fn close(&mut self) -> Result<(), Self::Error> {
+            self.stream.close().map_err(&mut self.f)
+        }
+    }
+
+    impl<S: crate::Read, E: crate::IoError> crate::Read for ErrInto<S, E> where
+        S::Error: Into<E>,
+    {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.stream.read(buf).map_err(Into::into)
+        }
+
+        #[inline]
+        fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+            self.stream.read_buf(buf).map_err(Into::into)
+        }
+
+        #[inline]
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Self::Error> {
+            self.stream.read_vectored(bufs).map_err(Into::into)
+        }
+    }
+
+    impl<S: crate::Write, E: crate::IoError> crate::Write for ErrInto<S, E> where
+        S::Error: Into<E>,
+    {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.stream.write(buf).map_err(Into::into)
+        }
+
+        #[inline]
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Self::Error> {
+            self.stream.write_vectored(bufs).map_err(Into::into)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.stream.flush().map_err(Into::into)
+        }
+
+        #[inline]
+        fn close(&mut self) -> Result<(), Self::Error> {
+            self.stream.close().map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use crate::{IoSlice, IoSliceMut, ReadBuf};
+    use super::{MapErr, ErrInto};
+
+    impl<S, F> MapErr<S, F> {
+        #[inline]
+        fn split_pin(self: Pin<&mut Self>) -> (Pin<&mut S>, &mut F) {
+            unsafe {
+                let this = self.get_unchecked_mut();
+                (Pin::new_unchecked(&mut this.stream), &mut this.f)
+            }
+        }
+    }
+
+    impl<S, E> ErrInto<S, E> {
+        #[inline]
+        fn stream_pin(self: Pin<&mut Self>) -> Pin<&mut S> {
+            unsafe {
+                self.map_unchecked_mut(|this| &mut this.stream)
+            }
+        }
+    }
+
+    impl<S: crate::AsyncRead, F: FnMut(S::Error) -> E, E: crate::IoError> crate::AsyncRead for MapErr<S, F> {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_read(cx, buf).map_err(f)
+        }
+
+        #[inline]
+        fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_read_buf(cx, buf).map_err(f)
+        }
+
+        #[inline]
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_read_vectored(cx, bufs).map_err(f)
+        }
+    }
+
+    impl<S: crate::AsyncWrite, F: FnMut(S::Error) -> E, E: crate::IoError> crate::AsyncWrite for MapErr<S, F> {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_write(cx, buf).map_err(f)
+        }
+
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_write_vectored(cx, bufs).map_err(f)
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            self.stream.is_write_vectored()
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_flush(cx).map_err(f)
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_close(cx).map_err(f)
+        }
+    }
+
+    impl<S: crate::AsyncSynchronous, F: FnMut(S::Error) -> E, E: crate::IoError> crate::AsyncSynchronous for MapErr<S, F> {
+        #[inline]
+        fn poll_read_write(self: Pin<&mut Self>, cx: &mut Context, buffer: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let (stream, f) = self.split_pin();
+            stream.poll_read_write(cx, buffer).map_err(f)
+        }
+    }
+
+    impl<S: crate::AsyncRead, E: crate::IoError> crate::AsyncRead for ErrInto<S, E> where
+        S::Error: Into<E>,
+    {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            self.stream_pin().poll_read(cx, buf).map_err(Into::into)
+        }
+
+        #[inline]
+        fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf<'_>) -> Poll<Result<(), Self::Error>> {
+            self.stream_pin().poll_read_buf(cx, buf).map_err(Into::into)
+        }
+
+        #[inline]
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &mut [IoSliceMut<'_>]) -> Poll<Result<usize, Self::Error>> {
+            self.stream_pin().poll_read_vectored(cx, bufs).map_err(Into::into)
+        }
+    }
+
+    impl<S: crate::AsyncWrite, E: crate::IoError> crate::AsyncWrite for ErrInto<S, E> where
+        S::Error: Into<E>,
+    {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            self.stream_pin().poll_write(cx, buf).map_err(Into::into)
+        }
+
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context, bufs: &[IoSlice<'_>]) -> Poll<Result<usize, Self::Error>> {
+            self.stream_pin().poll_write_vectored(cx, bufs).map_err(Into::into)
+        }
+
+        #[inline]
+        fn is_write_vectored(&self) -> bool {
+            self.stream.is_write_vectored()
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            self.stream_pin().poll_flush(cx).map_err(Into::into)
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            self.stream_pin().poll_close(cx).map_err(Into::into)
+        }
+    }
+
+    impl<S: crate::AsyncSynchronous, E: crate::IoError> crate::AsyncSynchronous for ErrInto<S, E> where
+        S::Error: Into<E>,
+    {
+        #[inline]
+        fn poll_read_write(self: Pin<&mut Self>, cx: &mut Context, buffer: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            self.stream_pin().poll_read_write(cx, buffer).map_err(Into::into)
+        }
+    }
+}
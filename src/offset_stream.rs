@@ -0,0 +1,179 @@
+use core::pin::Pin;
+use core::cmp;
+use crate::{ErrorType, SeekFrom, InvalidSeek};
+
+/// Presents a `ReadAt`/`AsyncReadAt` (and optionally `WriteAt`/`AsyncWriteAt`)
+/// device as a stream, tracking the current position itself.
+///
+/// `SeekFrom::End` is only supported when the device length was provided.
+/// Seeking requires the device error to convert from `InvalidSeek`, so that
+/// reads, writes and seeks share one error type.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct OffsetStream<D> {
+    device: D,
+    pos: u64,
+    len: Option<u64>,
+}
+
+impl<D> OffsetStream<D> {
+    pub const fn new(device: D) -> Self {
+        Self {
+            device,
+            pos: 0,
+            len: None,
+        }
+    }
+
+    pub const fn with_len(device: D, len: u64) -> Self {
+        Self {
+            device,
+            pos: 0,
+            len: Some(len),
+        }
+    }
+
+    pub fn into_inner(self) -> D {
+        self.device
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &D {
+        &self.device
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.device
+    }
+
+    #[inline]
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut D> {
+        unsafe {
+            self.map_unchecked_mut(|this| &mut this.device)
+        }
+    }
+
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Shortens `buf` to what is left of the device, if its length is known.
+    #[inline]
+    fn limit_read<'b>(&self, buf: &'b mut [u8]) -> &'b mut [u8] {
+        match self.len {
+            Some(len) => {
+                let rem = len.saturating_sub(self.pos);
+                let end = cmp::min(rem, buf.len() as u64) as usize;
+                unsafe { buf.get_unchecked_mut(..end) }
+            },
+            None => buf,
+        }
+    }
+
+    fn seek_to(&mut self, pos: SeekFrom) -> Result<u64, InvalidSeek> {
+        self.pos = crate::seek_position(pos, self.pos, self.len)?;
+        Ok(self.pos)
+    }
+}
+
+impl<D: ErrorType> ErrorType for OffsetStream<D> {
+    type Error = D::Error;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use crate::{SeekFrom, InvalidSeek};
+    use super::OffsetStream;
+
+    impl<D: crate::ReadAt> crate::Read for OffsetStream<D> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let buf = self.limit_read(buf);
+            let len = self.device.read_at(self.pos, buf)?;
+            self.pos = self.pos.saturating_add(len as u64);
+            Ok(len)
+        }
+    }
+
+    impl<D: crate::WriteAt> crate::Write for OffsetStream<D> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let len = self.device.write_at(self.pos, buf)?;
+            self.pos = self.pos.saturating_add(len as u64);
+            Ok(len)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.device.flush_at()
+        }
+    }
+
+    impl<D: crate::ErrorType> crate::Seek for OffsetStream<D> where
+        D::Error: From<InvalidSeek>,
+    {
+        type Error = D::Error;
+
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            self.seek_to(pos).map_err(From::from)
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use crate::{AsyncReadAt, AsyncWriteAt, SeekFrom, InvalidSeek};
+    use super::OffsetStream;
+
+    impl<D: AsyncReadAt> crate::AsyncRead for OffsetStream<D> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let buf = this.limit_read(buf);
+            let device = unsafe { Pin::new_unchecked(&mut this.device) };
+            let len = match device.poll_read_at(cx, this.pos, buf)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(len) => len,
+            };
+            this.pos = this.pos.saturating_add(len as u64);
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    impl<D: AsyncWriteAt> crate::AsyncWrite for OffsetStream<D> {
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let device = unsafe { Pin::new_unchecked(&mut this.device) };
+            let len = match device.poll_write_at(cx, this.pos, buf)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(len) => len,
+            };
+            this.pos = this.pos.saturating_add(len as u64);
+            Poll::Ready(Ok(len))
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            AsyncWriteAt::poll_flush_at(self.get_pin_mut(), cx)
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            AsyncWriteAt::poll_flush_at(self.get_pin_mut(), cx)
+        }
+    }
+
+    impl<D: crate::ErrorType> crate::AsyncSeek for OffsetStream<D> where
+        D::Error: From<InvalidSeek>,
+    {
+        type Error = D::Error;
+
+        #[inline]
+        fn poll_seek(self: Pin<&mut Self>, _: &mut Context, pos: SeekFrom) -> Poll<Result<u64, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            Poll::Ready(this.seek_to(pos).map_err(From::from))
+        }
+    }
+}
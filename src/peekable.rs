@@ -0,0 +1,237 @@
+use core::cmp;
+
+/// A reader with up to `N` bytes of lookahead.
+///
+/// Peeked bytes are kept until they are returned by a later read.
+#[derive(Debug, Copy, Clone)]
+pub struct Peekable<R, const N: usize> {
+    inner: R,
+    buffer: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+impl<R, const N: usize> Peekable<R, N> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: [0u8; N],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// The bytes peeked so far.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.pos <= self.filled && self.filled <= N);
+            self.buffer.get_unchecked(self.pos..self.filled)
+        }
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Any peeked data that has not yet been read is discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Moves the peeked bytes to the front and returns where the buffer must
+    /// be filled up to for `n` of them, if more are needed.
+    fn lookahead_end(&mut self, n: usize) -> Option<usize> {
+        let n = cmp::min(n, N);
+        if self.filled - self.pos >= n {
+            return None
+        }
+        if self.pos > 0 {
+            self.buffer.copy_within(self.pos..self.filled, 0);
+            self.filled -= self.pos;
+            self.pos = 0;
+        }
+        Some(n)
+    }
+
+    fn peeked(&self, n: usize) -> &[u8] {
+        let buffer = self.buffer();
+        buffer.get(..n).unwrap_or(buffer)
+    }
+
+    fn read_buffered(&mut self, buf: &mut [u8]) -> usize {
+        let mut rem = self.buffer();
+        let len = crate::slice_read(&mut rem, buf);
+        self.pos += len;
+        if self.pos == self.filled {
+            self.pos = 0;
+            self.filled = 0;
+        }
+        len
+    }
+}
+
+impl<R: crate::ErrorType, const N: usize> crate::ErrorType for Peekable<R, N> {
+    type Error = R::Error;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use super::Peekable;
+
+    impl<R: crate::Read, const N: usize> Peekable<R, N> {
+        /// Returns up to `n` upcoming bytes without consuming them.
+        ///
+        /// Fewer than `n` are returned only at EOF, or when `n` exceeds `N`.
+        pub fn peek(&mut self, n: usize) -> Result<&[u8], R::Error> {
+            while let Some(end) = self.lookahead_end(n) {
+                let space = unsafe { self.buffer.get_unchecked_mut(self.filled..end) };
+                match self.inner.read(space)? {
+                    0 => break,
+                    len => self.filled += len,
+                }
+            }
+            Ok(self.peeked(n))
+        }
+    }
+
+    impl<R: crate::Read, const N: usize> crate::Read for Peekable<R, N> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            match self.pos < self.filled {
+                true => Ok(self.read_buffered(buf)),
+                false => self.inner.read(buf),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use super::Peekable;
+
+    impl<R: crate::AsyncRead, const N: usize> Peekable<R, N> {
+        /// Returns up to `n` upcoming bytes without consuming them.
+        ///
+        /// Fewer than `n` are returned only at EOF, or when `n` exceeds `N`.
+        pub fn poll_peek(self: Pin<&mut Self>, cx: &mut Context, n: usize) -> Poll<Result<&[u8], R::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            while let Some(end) = this.lookahead_end(n) {
+                let space = unsafe { this.buffer.get_unchecked_mut(this.filled..end) };
+                let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+                match inner.poll_read(cx, space)? {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(0) => break,
+                    Poll::Ready(len) => this.filled += len,
+                }
+            }
+            Poll::Ready(Ok(this.peeked(n)))
+        }
+    }
+
+    impl<R: crate::AsyncRead, const N: usize> crate::AsyncRead for Peekable<R, N> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            match this.pos < this.filled {
+                true => Poll::Ready(Ok(this.read_buffered(buf))),
+                false => {
+                    let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+                    inner.poll_read(cx, buf)
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sync")]
+    mod sync_tests {
+        use core::convert::Infallible;
+        use crate::Read;
+        use super::super::Peekable;
+
+        /// Hands out at most two bytes per read.
+        struct Chunks<'a>(&'a [u8]);
+
+        impl crate::ErrorType for Chunks<'_> {
+            type Error = Infallible;
+        }
+
+        impl Read for Chunks<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                let len = buf.len().min(self.0.len()).min(2);
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0 = &self.0[len..];
+                Ok(len)
+            }
+        }
+
+        #[test]
+        fn peeked_bytes_are_replayed() {
+            let mut reader = Peekable::<_, 4>::new(Chunks(b"abcdef"));
+            assert_eq!(reader.peek(3), Ok(&b"abc"[..]));
+            assert_eq!(reader.peek(2), Ok(&b"ab"[..]));
+            let mut buf = [0u8; 6];
+            reader.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, b"abcdef");
+            assert_eq!(reader.read(&mut buf), Ok(0));
+        }
+
+        #[test]
+        fn partial_reads_keep_the_rest_peeked() {
+            let mut reader = Peekable::<_, 4>::new(Chunks(b"abcdef"));
+            assert_eq!(reader.peek(4), Ok(&b"abcd"[..]));
+            let mut buf = [0u8; 3];
+            assert_eq!(reader.read(&mut buf), Ok(3));
+            assert_eq!(&buf, b"abc");
+            assert_eq!(reader.peek(3), Ok(&b"def"[..]));
+            assert_eq!(reader.buffer(), b"def");
+            assert_eq!(reader.read(&mut buf), Ok(3));
+            assert_eq!(&buf, b"def");
+        }
+
+        #[test]
+        fn peek_is_capped_by_capacity_and_eof() {
+            let mut reader = Peekable::<_, 4>::new(Chunks(b"abcdef"));
+            assert_eq!(reader.peek(8), Ok(&b"abcd"[..]));
+            let mut reader = Peekable::<_, 4>::new(Chunks(b"ab"));
+            assert_eq!(reader.peek(4), Ok(&b"ab"[..]));
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod async_tests {
+        use core::task::{Context, Poll, Waker};
+        use core::pin::Pin;
+        use crate::AsyncRead;
+        use super::super::Peekable;
+
+        #[test]
+        fn peeked_bytes_are_replayed() {
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut reader = Peekable::<_, 4>::new(&b"abcdef"[..]);
+            match Pin::new(&mut reader).poll_peek(&mut cx, 3) {
+                Poll::Ready(Ok(peeked)) => assert_eq!(peeked, b"abc"),
+                _ => panic!("peek should be ready"),
+            }
+            let mut buf = [0u8; 8];
+            assert_eq!(Pin::new(&mut reader).poll_read(&mut cx, &mut buf), Poll::Ready(Ok(3)));
+            assert_eq!(&buf[..3], b"abc");
+            assert_eq!(Pin::new(&mut reader).poll_read(&mut cx, &mut buf), Poll::Ready(Ok(3)));
+            assert_eq!(&buf[..3], b"def");
+        }
+    }
+}
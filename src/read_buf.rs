@@ -0,0 +1,269 @@
+use core::mem::MaybeUninit;
+use core::{fmt, cmp};
+
+/// A cursor over a possibly uninitialized buffer, tracking how much of it has
+/// been filled with data and how much is known to be initialized.
+///
+/// `filled <= initialized <= capacity` always holds.
+pub struct ReadBuf<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+    initialized: usize,
+}
+
+impl<'a> ReadBuf<'a> {
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        let initialized = buf.len();
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        Self {
+            buf,
+            filled: 0,
+            initialized,
+        }
+    }
+
+    #[inline]
+    pub fn uninit(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            buf,
+            filled: 0,
+            initialized: 0,
+        }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.filled
+    }
+
+    #[inline]
+    pub fn filled(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.filled <= self.initialized);
+            slice_assume_init(self.buf.get_unchecked(..self.filled))
+        }
+    }
+
+    #[inline]
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            debug_assert!(self.filled <= self.initialized);
+            slice_assume_init_mut(self.buf.get_unchecked_mut(..self.filled))
+        }
+    }
+
+    #[inline]
+    pub fn initialized(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.initialized <= self.buf.len());
+            slice_assume_init(self.buf.get_unchecked(..self.initialized))
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The caller must not write uninitialized bytes into the returned slice.
+    #[inline]
+    pub unsafe fn unfilled_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        self.buf.get_unchecked_mut(self.filled..)
+    }
+
+    /// Zeroes any part of the unfilled region that is not yet initialized,
+    /// and returns all of it.
+    #[inline]
+    pub fn initialize_unfilled(&mut self) -> &mut [u8] {
+        self.initialize_unfilled_to(self.remaining())
+    }
+
+    /// Panics if `n` is larger than `remaining()`.
+    pub fn initialize_unfilled_to(&mut self, n: usize) -> &mut [u8] {
+        assert!(n <= self.remaining(), "n overflows remaining");
+        let end = self.filled + n;
+        if self.initialized < end {
+            unsafe {
+                self.buf.get_unchecked_mut(self.initialized..end)
+                    .iter_mut().for_each(|b| *b = MaybeUninit::new(0));
+            }
+            self.initialized = end;
+        }
+        unsafe {
+            slice_assume_init_mut(self.buf.get_unchecked_mut(self.filled..end))
+        }
+    }
+
+    /// Asserts that the first `n` unfilled bytes are initialized.
+    ///
+    /// # Safety
+    ///
+    /// Those bytes must actually have been initialized.
+    #[inline]
+    pub unsafe fn assume_init(&mut self, n: usize) {
+        let end = cmp::min(self.filled + n, self.buf.len());
+        self.initialized = cmp::max(self.initialized, end);
+    }
+
+    /// Marks `n` more bytes as filled. Panics if they are not initialized.
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        let filled = self.filled.checked_add(n).expect("filled overflow");
+        self.set_filled(filled)
+    }
+
+    /// Panics if `n` is beyond the initialized region.
+    #[inline]
+    pub fn set_filled(&mut self, n: usize) {
+        assert!(n <= self.initialized, "filled must not become larger than initialized");
+        self.filled = n;
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.filled = 0;
+    }
+
+    /// Panics if `buf` does not fit in the remaining space.
+    pub fn put_slice(&mut self, buf: &[u8]) {
+        assert!(buf.len() <= self.remaining(), "buf.len() must fit in remaining()");
+        let end = self.filled + buf.len();
+        unsafe {
+            let dst = self.buf.get_unchecked_mut(self.filled..end);
+            core::ptr::copy_nonoverlapping(buf.as_ptr(), dst.as_mut_ptr() as *mut u8, buf.len());
+        }
+        self.initialized = cmp::max(self.initialized, end);
+        self.filled = end;
+    }
+
+    /// Fills the remaining space with `value`.
+    pub fn fill(&mut self, value: u8) {
+        unsafe {
+            self.buf.get_unchecked_mut(self.filled..)
+                .iter_mut().for_each(|b| *b = MaybeUninit::new(value));
+        }
+        self.filled = self.buf.len();
+        self.initialized = self.buf.len();
+    }
+
+    /// The memory `self` covers, so that callers can tell whether a reader
+    /// replaced the `ReadBuf` they passed in with one of its own.
+    #[cfg(any(feature = "sync", feature = "async"))]
+    #[inline]
+    pub(crate) fn identity(&self) -> (*const MaybeUninit<u8>, usize) {
+        (self.buf.as_ptr(), self.buf.len())
+    }
+
+    /// Runs `f` against a `ReadBuf` over at most `limit` bytes of the
+    /// remaining space, then carries its progress back into `self`.
+    ///
+    /// Panics if `f` swaps the `ReadBuf` it is given for another one, whose
+    /// progress would say nothing about the memory of `self`.
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn with_limit<R, F: FnOnce(&mut ReadBuf<'_>) -> R>(&mut self, limit: usize, f: F) -> R {
+        let limit = cmp::min(limit, self.remaining());
+        let initialized = cmp::min(self.initialized - self.filled, limit);
+        let (res, filled, init) = {
+            let unfilled = unsafe { self.buf.get_unchecked_mut(self.filled..self.filled + limit) };
+            let mut sub = ReadBuf::uninit(unfilled);
+            sub.initialized = initialized;
+            let identity = sub.identity();
+            let res = f(&mut sub);
+            assert!(sub.identity() == identity, "the ReadBuf was replaced by the reader");
+            (res, sub.filled, sub.initialized)
+        };
+        unsafe {
+            self.assume_init(init);
+        }
+        self.advance(filled);
+        res
+    }
+}
+
+impl<'a> fmt::Debug for ReadBuf<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBuf")
+            .field("filled", &self.filled)
+            .field("initialized", &self.initialized)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+#[inline]
+unsafe fn slice_assume_init(buf: &[MaybeUninit<u8>]) -> &[u8] {
+    &*(buf as *const [MaybeUninit<u8>] as *const [u8])
+}
+
+#[inline]
+unsafe fn slice_assume_init_mut(buf: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    &mut *(buf as *mut [MaybeUninit<u8>] as *mut [u8])
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+    use super::ReadBuf;
+
+    #[test]
+    fn put_slice_fills_and_initializes() {
+        let mut storage = [MaybeUninit::uninit(); 8];
+        let mut buf = ReadBuf::uninit(&mut storage);
+        buf.put_slice(b"abc");
+        assert_eq!(buf.filled(), b"abc");
+        assert_eq!(buf.initialized(), b"abc");
+        assert_eq!(buf.remaining(), 5);
+    }
+
+    #[test]
+    fn initialize_unfilled_only_zeroes_what_is_needed() {
+        let mut storage = [MaybeUninit::uninit(); 8];
+        let mut buf = ReadBuf::uninit(&mut storage);
+        buf.initialize_unfilled_to(2).copy_from_slice(b"ab");
+        assert_eq!(buf.initialized().len(), 2);
+        assert!(buf.filled().is_empty());
+        assert_eq!(buf.initialize_unfilled_to(4), b"ab\0\0");
+        assert_eq!(buf.initialized().len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "filled must not become larger than initialized")]
+    fn advance_past_initialized_panics() {
+        let mut storage = [MaybeUninit::uninit(); 8];
+        let mut buf = ReadBuf::uninit(&mut storage);
+        buf.advance(1);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn with_limit_carries_progress_back() {
+        let mut storage = [MaybeUninit::uninit(); 8];
+        let mut buf = ReadBuf::uninit(&mut storage);
+        buf.put_slice(b"ab");
+        buf.initialize_unfilled_to(3);
+        let len = buf.with_limit(4, |sub| {
+            assert_eq!(sub.capacity(), 4);
+            assert_eq!(sub.initialized().len(), 3);
+            sub.put_slice(b"c");
+            sub.filled().len()
+        });
+        assert_eq!(len, 1);
+        assert_eq!(buf.filled(), b"abc");
+        assert_eq!(buf.initialized().len(), 5);
+    }
+
+    #[cfg(all(feature = "sync", feature = "std"))]
+    #[test]
+    #[should_panic(expected = "the ReadBuf was replaced by the reader")]
+    fn with_limit_rejects_a_replaced_buffer() {
+        let mut storage = [MaybeUninit::uninit(); 8];
+        let mut buf = ReadBuf::uninit(&mut storage);
+        buf.with_limit(4, |sub| {
+            *sub = ReadBuf::new(Box::leak(Box::new([1u8; 16])));
+            sub.advance(16);
+        });
+    }
+}
@@ -0,0 +1,283 @@
+use core::{fmt, str, cmp};
+use crate::{IoError, ErrorKind};
+use super::BufRead;
+
+pub trait BufReadExt: BufRead {
+    /// Reads into `buf` up to and including `delim`, or until EOF.
+    ///
+    /// Fails with `ReadUntilError::Truncated` if `buf` fills up before either
+    /// the delimiter or EOF is found, in which case all of `buf` has been read into.
+    fn read_until(&mut self, delim: u8, buf: &mut [u8]) -> Result<usize, ReadUntilError<Self::Error>> {
+        let mut read = 0usize;
+        loop {
+            let (done, used) = {
+                let available = match self.fill_buf() {
+                    Ok(available) => available,
+                    Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => break Err(e.into()),
+                };
+                if available.is_empty() {
+                    break Ok(read)
+                }
+                let rem = unsafe {
+                    debug_assert!(read <= buf.len());
+                    buf.get_unchecked_mut(read..)
+                };
+                if rem.is_empty() {
+                    break Err(ReadUntilError::Truncated)
+                }
+                match available.iter().position(|&b| b == delim) {
+                    Some(i) if i < rem.len() => {
+                        rem[..=i].copy_from_slice(&available[..=i]);
+                        (true, i + 1)
+                    },
+                    _ => {
+                        let len = cmp::min(rem.len(), available.len());
+                        rem[..len].copy_from_slice(&available[..len]);
+                        (false, len)
+                    },
+                }
+            };
+            self.consume(used);
+            read += used;
+            if done {
+                break Ok(read)
+            }
+        }
+    }
+
+    /// Reads a line, including its trailing `\n`, into `buf`.
+    fn read_line<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str, ReadLineError<Self::Error>> {
+        let len = self.read_until(b'\n', buf)?;
+        str::from_utf8(&buf[..len])
+            .map_err(ReadLineError::Utf8)
+    }
+
+    fn lines(self, buffer: &mut [u8]) -> Lines<'_, Self> where Self: Sized {
+        Lines {
+            reader: self,
+            buffer,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn read_until_vec(&mut self, delim: u8, buf: &mut Vec<u8>) -> Result<usize, Self::Error> {
+        let mut read = 0usize;
+        loop {
+            let (done, used) = {
+                let available = match self.fill_buf() {
+                    Ok(available) => available,
+                    Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => break Err(e),
+                };
+                match available.iter().position(|&b| b == delim) {
+                    Some(i) => {
+                        buf.extend_from_slice(&available[..=i]);
+                        (true, i + 1)
+                    },
+                    None => {
+                        buf.extend_from_slice(available);
+                        (available.is_empty(), available.len())
+                    },
+                }
+            };
+            self.consume(used);
+            read += used;
+            if done {
+                break Ok(read)
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn read_line_string(&mut self, buf: &mut String) -> Result<usize, ReadLineError<Self::Error>> {
+        let mut bytes = Vec::new();
+        let len = self.read_until_vec(b'\n', &mut bytes)?;
+        buf.push_str(str::from_utf8(&bytes).map_err(ReadLineError::Utf8)?);
+        Ok(len)
+    }
+}
+
+impl<T: ?Sized + BufRead> BufReadExt for T { }
+
+/// Reads lines into a caller-provided buffer, stripping the line terminator.
+///
+/// Each line borrows that buffer, so this is driven with `next_line` rather
+/// than being an `Iterator`.
+pub struct Lines<'b, B> {
+    reader: B,
+    buffer: &'b mut [u8],
+}
+
+impl<'b, B> Lines<'b, B> {
+    pub fn into_inner(self) -> B {
+        self.reader
+    }
+}
+
+impl<'b, B: BufRead> Lines<'b, B> {
+    /// A line that does not fit in the buffer yields `ReadLineError::Truncated`,
+    /// and the remainder of it is returned by the following call.
+    pub fn next_line(&mut self) -> Option<Result<&str, ReadLineError<B::Error>>> {
+        let len = match self.reader.read_until(b'\n', self.buffer) {
+            Ok(0) => return None,
+            Ok(len) => len,
+            Err(e) => return Some(Err(e.into())),
+        };
+        let mut line = &self.buffer[..len];
+        if let Some(rem) = line.strip_suffix(b"\n") {
+            line = rem.strip_suffix(b"\r").unwrap_or(rem);
+        }
+        Some(str::from_utf8(line).map_err(ReadLineError::Utf8))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReadUntilError<E> {
+    Truncated,
+    Io(E),
+}
+
+impl<E> From<E> for ReadUntilError<E> {
+    #[inline]
+    fn from(e: E) -> Self {
+        ReadUntilError::Io(e)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ReadUntilError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadUntilError::Truncated => f.write_str("buffer full before delimiter"),
+            ReadUntilError::Io(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ReadUntilError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadUntilError::Truncated => None,
+            ReadUntilError::Io(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReadLineError<E> {
+    Truncated,
+    Utf8(str::Utf8Error),
+    Io(E),
+}
+
+impl<E> From<E> for ReadLineError<E> {
+    #[inline]
+    fn from(e: E) -> Self {
+        ReadLineError::Io(e)
+    }
+}
+
+impl<E> From<ReadUntilError<E>> for ReadLineError<E> {
+    #[inline]
+    fn from(e: ReadUntilError<E>) -> Self {
+        match e {
+            ReadUntilError::Truncated => ReadLineError::Truncated,
+            ReadUntilError::Io(e) => ReadLineError::Io(e),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ReadLineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadLineError::Truncated => f.write_str("buffer full before end of line"),
+            ReadLineError::Utf8(e) => fmt::Display::fmt(e, f),
+            ReadLineError::Io(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ReadLineError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadLineError::Truncated => None,
+            ReadLineError::Utf8(e) => Some(e),
+            ReadLineError::Io(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BufReader;
+    use super::{BufReadExt, ReadLineError, ReadUntilError};
+
+    #[test]
+    fn read_until_includes_the_delimiter() {
+        let mut reader = &b"ab,cd"[..];
+        let mut buf = [0u8; 8];
+        assert_eq!(reader.read_until(b',', &mut buf), Ok(3));
+        assert_eq!(&buf[..3], b"ab,");
+        assert_eq!(reader.read_until(b',', &mut buf), Ok(2));
+        assert_eq!(&buf[..2], b"cd");
+        assert_eq!(reader.read_until(b',', &mut buf), Ok(0));
+    }
+
+    #[test]
+    fn read_until_spans_refills() {
+        let mut reader = BufReader::<_, 2>::new(&b"abcde,f"[..]);
+        let mut buf = [0u8; 8];
+        assert_eq!(reader.read_until(b',', &mut buf), Ok(6));
+        assert_eq!(&buf[..6], b"abcde,");
+    }
+
+    #[test]
+    fn read_until_reports_truncation() {
+        let mut reader = &b"abcd,e"[..];
+        let mut buf = [0u8; 3];
+        assert_eq!(reader.read_until(b',', &mut buf), Err(ReadUntilError::Truncated));
+        assert_eq!(&buf, b"abc");
+        assert_eq!(reader.read_until(b',', &mut buf), Ok(2));
+        assert_eq!(&buf[..2], b"d,");
+    }
+
+    #[test]
+    fn read_until_fills_the_buffer_exactly() {
+        let mut reader = &b"abc,"[..];
+        let mut buf = [0u8; 4];
+        assert_eq!(reader.read_until(b',', &mut buf), Ok(4));
+        assert_eq!(&buf, b"abc,");
+    }
+
+    #[test]
+    fn lines_strip_terminators() {
+        let mut buf = [0u8; 8];
+        let mut lines = BufReader::<_, 4>::new(&b"one\r\ntwo\n\nthree"[..]).lines(&mut buf);
+        assert_eq!(lines.next_line(), Some(Ok("one")));
+        assert_eq!(lines.next_line(), Some(Ok("two")));
+        assert_eq!(lines.next_line(), Some(Ok("")));
+        assert_eq!(lines.next_line(), Some(Ok("three")));
+        assert_eq!(lines.next_line(), None);
+    }
+
+    #[test]
+    fn lines_resume_after_truncation() {
+        let mut buf = [0u8; 4];
+        let mut lines = (&b"toolong\nx\n"[..]).lines(&mut buf);
+        assert_eq!(lines.next_line(), Some(Err(ReadLineError::Truncated)));
+        assert_eq!(lines.next_line(), Some(Ok("ong")));
+        assert_eq!(lines.next_line(), Some(Ok("x")));
+        assert_eq!(lines.next_line(), None);
+    }
+
+    #[test]
+    fn lines_report_invalid_utf8() {
+        let mut buf = [0u8; 4];
+        let mut lines = (&b"\xff\nok"[..]).lines(&mut buf);
+        assert!(matches!(lines.next_line(), Some(Err(ReadLineError::Utf8(_)))));
+        assert_eq!(lines.next_line(), Some(Ok("ok")));
+        assert_eq!(lines.next_line(), None);
+    }
+}
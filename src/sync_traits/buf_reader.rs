@@ -0,0 +1,160 @@
+use core::cmp;
+use crate::ErrorType;
+use super::{Read, BufRead};
+
+#[derive(Debug)]
+pub struct BufReader<R, const N: usize> {
+    inner: R,
+    buffer: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+impl<R, const N: usize> BufReader<R, N> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: [0u8; N],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.pos <= self.filled && self.filled <= N);
+            self.buffer.get_unchecked(self.pos..self.filled)
+        }
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Any buffered data that has not yet been read is discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    #[inline]
+    fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.filled = 0;
+    }
+}
+
+impl<R: ErrorType, const N: usize> ErrorType for BufReader<R, N> {
+    type Error = R::Error;
+}
+
+impl<R: Read, const N: usize> Read for BufReader<R, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // bypass the internal buffer entirely for large reads
+        if self.pos == self.filled && buf.len() >= N {
+            self.discard_buffer();
+            return self.inner.read(buf)
+        }
+
+        let len = {
+            let mut rem = self.fill_buf()?;
+            crate::slice_read(&mut rem, buf)
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: Read, const N: usize> BufRead for BufReader<R, N> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.pos >= self.filled {
+            debug_assert_eq!(self.pos, self.filled);
+            let len = self.inner.read(&mut self.buffer)?;
+            debug_assert!(len <= N);
+            self.filled = cmp::min(len, N);
+            self.pos = 0;
+        }
+        Ok(self.buffer())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos.saturating_add(amt), self.filled);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use crate::{ErrorType, Read, BufRead};
+    use super::BufReader;
+
+    /// Counts the reads that reach the underlying slice.
+    struct Calls<'a> {
+        data: &'a [u8],
+        calls: usize,
+    }
+
+    impl ErrorType for Calls<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for Calls<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.calls += 1;
+            self.data.read(buf)
+        }
+    }
+
+    #[test]
+    fn small_reads_are_batched() {
+        let mut reader = BufReader::<_, 4>::new(Calls { data: b"abcdef", calls: 0 });
+        let mut buf = [0u8; 2];
+        assert_eq!(reader.read(&mut buf), Ok(2));
+        assert_eq!(&buf, b"ab");
+        assert_eq!(reader.read(&mut buf), Ok(2));
+        assert_eq!(&buf, b"cd");
+        assert_eq!(reader.get_ref().calls, 1);
+        assert_eq!(reader.read(&mut buf), Ok(2));
+        assert_eq!(&buf, b"ef");
+        assert_eq!(reader.read(&mut buf), Ok(0));
+        assert_eq!(reader.get_ref().calls, 3);
+    }
+
+    #[test]
+    fn large_reads_bypass_an_empty_buffer() {
+        let mut reader = BufReader::<_, 4>::new(Calls { data: b"abcdefgh", calls: 0 });
+        let mut buf = [0u8; 2];
+        assert_eq!(reader.read(&mut buf), Ok(2));
+        let mut large = [0u8; 4];
+        assert_eq!(reader.read(&mut large), Ok(2));
+        assert_eq!(&large[..2], b"cd");
+        assert_eq!(reader.read(&mut large), Ok(4));
+        assert_eq!(&large, b"efgh");
+        assert!(reader.buffer().is_empty());
+        assert_eq!(reader.get_ref().calls, 2);
+    }
+
+    #[test]
+    fn consume_advances_the_buffer() {
+        let mut reader = BufReader::<_, 4>::new(&b"abcdef"[..]);
+        assert_eq!(reader.fill_buf(), Ok(&b"abcd"[..]));
+        reader.consume(3);
+        assert_eq!(reader.fill_buf(), Ok(&b"d"[..]));
+        reader.consume(8);
+        assert_eq!(reader.fill_buf(), Ok(&b"ef"[..]));
+        reader.consume(2);
+        assert_eq!(reader.fill_buf(), Ok(&b""[..]));
+    }
+}
@@ -0,0 +1,164 @@
+use core::cmp;
+use crate::{AllError, ErrorType, IoError, ErrorKind, IntoInnerError};
+use super::Write;
+
+/// Buffered data is only written out when the buffer fills up or on `flush`;
+/// it is *not* flushed on drop.
+#[derive(Debug)]
+pub struct BufWriter<W, const N: usize> {
+    inner: W,
+    buffer: [u8; N],
+    filled: usize,
+}
+
+impl<W, const N: usize> BufWriter<W, N> {
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: [0u8; N],
+            filled: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        unsafe {
+            debug_assert!(self.filled <= N);
+            self.buffer.get_unchecked(..self.filled)
+        }
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn spare_capacity(&self) -> usize {
+        N - self.filled
+    }
+
+    pub(crate) fn write_to_buf(&mut self, buf: &[u8]) -> usize {
+        let len = cmp::min(buf.len(), self.spare_capacity());
+        unsafe {
+            self.buffer.get_unchecked_mut(self.filled..self.filled + len)
+                .copy_from_slice(buf.get_unchecked(..len));
+        }
+        self.filled += len;
+        len
+    }
+}
+
+impl<W: Write, const N: usize> BufWriter<W, N> {
+    pub(crate) fn flush_buf(&mut self) -> Result<(), AllError<W::Error>> {
+        let mut written = 0usize;
+        let res = loop {
+            let buf = unsafe {
+                debug_assert!(written <= self.filled);
+                self.buffer.get_unchecked(written..self.filled)
+            };
+            if buf.is_empty() {
+                break Ok(())
+            }
+            match self.inner.write(buf) {
+                Ok(0) => break Err(AllError::WriteZero),
+                Ok(n) => written += cmp::min(n, buf.len()),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => break Err(AllError::Io(e)),
+            }
+        };
+        self.buffer.copy_within(written..self.filled, 0);
+        self.filled -= written;
+        res
+    }
+
+    /// Flushes the buffer and unwraps the inner writer.
+    ///
+    /// On failure the returned error still holds the writer along with any
+    /// bytes that could not be written out.
+    pub fn into_inner(mut self) -> Result<W, IntoInnerError<Self, AllError<W::Error>>> {
+        match self.flush_buf() {
+            Ok(()) => Ok(self.inner),
+            Err(error) => Err(IntoInnerError::new(self, error)),
+        }
+    }
+}
+
+/// Flushing fails with `AllError::WriteZero` if the inner writer stops
+/// accepting data.
+impl<W: ErrorType, const N: usize> ErrorType for BufWriter<W, N> {
+    type Error = AllError<W::Error>;
+}
+
+impl<W: Write, const N: usize> Write for BufWriter<W, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.len() > self.spare_capacity() {
+            self.flush_buf()?;
+        }
+
+        // bypass the internal buffer entirely for large writes
+        if buf.len() >= N && self.filled == 0 {
+            return self.inner.write(buf).map_err(From::from)
+        }
+
+        Ok(self.write_to_buf(buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flush_buf()?;
+        self.inner.flush().map_err(From::from)
+    }
+
+    fn close(&mut self) -> Result<(), Self::Error> {
+        self.flush_buf()?;
+        self.inner.close().map_err(From::from)
+    }
+}
+
+impl<W, const N: usize, E> IntoInnerError<BufWriter<W, N>, E> {
+    /// The bytes that were still buffered when the final flush failed.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        self.writer().buffer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AllError, Write};
+    use super::BufWriter;
+
+    #[test]
+    fn small_writes_are_batched() {
+        let mut out = [0u8; 8];
+        let mut writer = BufWriter::<_, 4>::new(&mut out[..]);
+        assert_eq!(writer.write(b"ab"), Ok(2));
+        assert_eq!(writer.write(b"cd"), Ok(2));
+        assert_eq!(writer.buffer(), b"abcd");
+        assert_eq!(writer.write(b"e"), Ok(1));
+        assert_eq!(writer.buffer(), b"e");
+        assert_eq!(writer.flush(), Ok(()));
+        assert_eq!(&out[..5], b"abcde");
+    }
+
+    #[test]
+    fn into_inner_hands_back_unflushed_bytes() {
+        let mut out = [0u8; 2];
+        let mut writer = BufWriter::<_, 4>::new(&mut out[..]);
+        assert_eq!(writer.write(b"abc"), Ok(3));
+        let e = writer.into_inner().unwrap_err();
+        assert_eq!(e.error(), &AllError::Io(AllError::UnexpectedEof));
+        assert_eq!(e.buffer(), b"c");
+        assert_eq!(&out, b"ab");
+    }
+}
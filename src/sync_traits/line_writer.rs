@@ -0,0 +1,175 @@
+use crate::{AllError, ErrorType, IntoInnerError};
+use super::{Write, BufWriter};
+
+/// Like `BufWriter`, but any complete lines are written through to the inner
+/// writer as soon as a `\n` is seen.
+#[derive(Debug)]
+pub struct LineWriter<W, const N: usize> {
+    inner: BufWriter<W, N>,
+}
+
+impl<W, const N: usize> LineWriter<W, N> {
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner: BufWriter::new(inner),
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        self.inner.buffer()
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<W: Write, const N: usize> LineWriter<W, N> {
+    pub fn into_inner(self) -> Result<W, IntoInnerError<Self, AllError<W::Error>>> {
+        self.inner.into_inner().map_err(|e| {
+            let (error, inner) = e.into_parts();
+            IntoInnerError::new(Self { inner }, error)
+        })
+    }
+}
+
+impl<W: ErrorType, const N: usize> ErrorType for LineWriter<W, N> {
+    type Error = AllError<W::Error>;
+}
+
+impl<W: Write, const N: usize> Write for LineWriter<W, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let newline = match buf.iter().rposition(|&b| b == b'\n') {
+            Some(i) => i + 1,
+            None => {
+                // a previously completed line may still be waiting on the inner writer
+                if self.inner.buffer().last() == Some(&b'\n') {
+                    self.inner.flush_buf()?;
+                }
+                return self.inner.write(buf)
+            },
+        };
+
+        // the new lines go straight to the inner writer, so nothing may still
+        // be buffered ahead of them
+        self.inner.flush_buf()?;
+        debug_assert!(self.inner.buffer().is_empty());
+        let (lines, tail) = buf.split_at(newline);
+        let len = self.inner.get_mut().write(lines)?;
+        if len < lines.len() {
+            return Ok(len)
+        }
+
+        Ok(len + self.inner.write_to_buf(tail))
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+
+    #[inline]
+    fn close(&mut self) -> Result<(), Self::Error> {
+        self.inner.close()
+    }
+}
+
+impl<W, const N: usize, E> IntoInnerError<LineWriter<W, N>, E> {
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        self.writer().buffer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrorKind, ErrorType, Write};
+    use super::LineWriter;
+
+    /// Records what it is given, accepting at most `chunk` bytes per write.
+    struct Log {
+        data: [u8; 32],
+        len: usize,
+        chunk: usize,
+    }
+
+    impl Log {
+        fn new(chunk: usize) -> Self {
+            Self { data: [0; 32], len: 0, chunk }
+        }
+
+        fn data(&self) -> &[u8] {
+            &self.data[..self.len]
+        }
+    }
+
+    impl ErrorType for Log {
+        type Error = ErrorKind;
+    }
+
+    impl Write for Log {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let n = buf.len().min(self.chunk);
+            self.data[self.len..self.len + n].copy_from_slice(&buf[..n]);
+            self.len += n;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn partial_lines_stay_buffered() {
+        let mut writer = LineWriter::<_, 8>::new(Log::new(32));
+        assert_eq!(writer.write(b"ab"), Ok(2));
+        assert_eq!(writer.write(b"c"), Ok(1));
+        assert_eq!(writer.get_ref().data(), b"");
+        assert_eq!(writer.buffer(), b"abc");
+    }
+
+    #[test]
+    fn buffered_data_goes_out_before_the_line() {
+        let mut writer = LineWriter::<_, 8>::new(Log::new(32));
+        assert_eq!(writer.write(b"ab"), Ok(2));
+        assert_eq!(writer.write(b"c\nd\ne"), Ok(5));
+        assert_eq!(writer.get_ref().data(), b"abc\nd\n");
+        assert_eq!(writer.buffer(), b"e");
+        assert_eq!(writer.flush(), Ok(()));
+        assert_eq!(writer.get_ref().data(), b"abc\nd\ne");
+    }
+
+    #[test]
+    fn short_inner_writes_keep_the_order() {
+        let mut writer = LineWriter::<_, 8>::new(Log::new(2));
+        assert_eq!(writer.write_all(b"ab"), Ok(()));
+        assert_eq!(writer.write_all(b"cd\nef\ng"), Ok(()));
+        assert_eq!(writer.get_ref().data(), b"abcd\nef\n");
+        assert_eq!(writer.buffer(), b"g");
+        assert_eq!(writer.flush(), Ok(()));
+        assert_eq!(writer.get_ref().data(), b"abcd\nef\ng");
+    }
+
+    #[test]
+    fn write_fmt_flushes_at_newlines() {
+        let mut writer = LineWriter::<_, 16>::new(Log::new(32));
+        assert!(writer.write_fmt(format_args!("{}-{}\n{}", 1, 2, 3)).is_ok());
+        assert_eq!(writer.get_ref().data(), b"1-2\n");
+        assert_eq!(writer.buffer(), b"3");
+        assert!(writer.write_fmt(format_args!("{}\n", '!')).is_ok());
+        assert_eq!(writer.get_ref().data(), b"1-2\n3!\n");
+    }
+}
@@ -0,0 +1,619 @@
+use core::{fmt, cmp};
+use super::{AllError, ErrorType, IoError, ErrorKind, Take, Chain, Fuse, Bytes, Chars, SeekFrom, IoSlice, IoSliceMut, ReadBuf};
+#[cfg(feature = "ufmt")]
+use super::uWriter;
+
+pub(crate) mod prelude {
+    pub use super::{Read, ReadExt, BufRead, BufReadExt, Write, WriteExt, Seek, ReadAt, WriteAt};
+}
+
+mod buf_read;
+pub use buf_read::*;
+mod buf_reader;
+pub use buf_reader::*;
+mod buf_writer;
+pub use buf_writer::*;
+mod line_writer;
+pub use line_writer::*;
+mod read_at;
+pub use read_at::*;
+
+// TODO: pull the provided fns out into extension traits instead?
+
+pub trait Read: ErrorType {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<(), AllError<Self::Error>> {
+        // impl stolen from std
+        let len = buf.len();
+        while !buf.is_empty() {
+            match self.read(buf) {
+                Ok(0) => break,
+                Ok(n) => { let tmp = buf; buf = &mut tmp[n..]; }
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(AllError::from_io(e, len - buf.len())),
+            }
+        }
+        if !buf.is_empty() {
+            Err(AllError::UnexpectedEof)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reads into the unfilled part of `buf`. The default implementation only
+    /// zeroes whatever part of it has not been initialized yet.
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+        let len = self.read(buf.initialize_unfilled())?;
+        buf.advance(len);
+        Ok(())
+    }
+
+    /// The default implementation reads into the first non-empty buffer.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Self::Error> {
+        match bufs.iter_mut().find(|b| !b.is_empty()) {
+            Some(buf) => self.read(buf),
+            None => self.read(&mut []),
+        }
+    }
+
+    fn take(self, limit: usize) -> Take<Self> where Self: Sized {
+        Take::new(self, limit)
+    }
+}
+
+impl<T: ?Sized + Read> Read for &'_ mut T {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Read::read(*self, buf)
+    }
+
+    #[inline]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+        Read::read_buf(*self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Self::Error> {
+        Read::read_vectored(*self, bufs)
+    }
+}
+
+impl Read for &'_ [u8] {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(crate::slice_read(self, buf))
+    }
+
+    #[inline]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+        let len = cmp::min(self.len(), buf.remaining());
+        let (head, tail) = self.split_at(len);
+        buf.put_slice(head);
+        *self = tail;
+        Ok(())
+    }
+}
+
+pub trait BufRead: Read {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error>;
+
+    fn consume(&mut self, amt: usize);
+}
+
+impl<T: ?Sized + BufRead> BufRead for &'_ mut T {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        BufRead::fill_buf(*self)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        BufRead::consume(*self, amt)
+    }
+}
+
+impl BufRead for &'_ [u8] {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(*self)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        *self = self.get(amt..).unwrap_or(&[]);
+    }
+}
+
+pub trait Write: ErrorType {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error>;
+
+    fn flush(&mut self) -> Result<(), Self::Error>;
+
+    /// Signals that nothing more will be written, letting the stream emit any
+    /// trailer. The default implementation only flushes.
+    fn close(&mut self) -> Result<(), Self::Error> {
+        self.flush()
+    }
+
+    /// Fails with `AllError::WriteZero` if the stream stops accepting data.
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<(), AllError<Self::Error>> {
+        let len = buf.len();
+        loop {
+            match self.write(buf) {
+                Ok(n) if n >= buf.len() => {
+                    debug_assert_eq!(n, buf.len());
+                    return Ok(())
+                },
+                Ok(0) => return Err(AllError::WriteZero),
+                Ok(n) => buf = unsafe { buf.get_unchecked(n..) },
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(AllError::from_io(e, len - buf.len())),
+            }
+        }
+    }
+
+    /// The default implementation writes out the first non-empty buffer.
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Self::Error> {
+        match bufs.iter().find(|b| !b.is_empty()) {
+            Some(buf) => self.write(buf),
+            None => self.write(&[]),
+        }
+    }
+
+    fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> Result<(), AllError<Self::Error>> {
+        IoSlice::advance_slices(&mut bufs, 0);
+        let mut done = 0usize;
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs) {
+                Ok(0) => return Err(AllError::WriteZero),
+                Ok(n) => {
+                    IoSlice::advance_slices(&mut bufs, n);
+                    done = done.saturating_add(n);
+                },
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(AllError::from_io(e, done)),
+            }
+        }
+        Ok(())
+    }
+
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<(), WriteFmtError<AllError<Self::Error>>> {
+        // impl stolen from std
+        struct Adaptor<'a, T: ?Sized + 'a, E> {
+            inner: &'a mut T,
+            error: Option<E>,
+        }
+
+        impl<T: Write + ?Sized> fmt::Write for Adaptor<'_, T, AllError<T::Error>> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                match self.inner.write_all(s.as_bytes()) {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        self.error = Some(e);
+                        Err(fmt::Error)
+                    }
+                }
+            }
+        }
+
+        let mut output = Adaptor { inner: self, error: None::<AllError<Self::Error>> };
+        match fmt::write(&mut output, fmt) {
+            Ok(()) => Ok(()),
+            Err(..) => Err(match output.error.take() {
+                Some(e) => WriteFmtError::Io(e),
+                None => WriteFmtError::FormatterError,
+            }),
+        }
+    }
+
+    fn take(self, limit: usize) -> Take<Self> where Self: Sized {
+        Take::new(self, limit)
+    }
+
+    #[cfg(feature = "ufmt")]
+    fn uwriter(self) -> uWriter<Self> where Self: Sized {
+        uWriter::new(self)
+    }
+}
+
+impl<T: ?Sized + Write> Write for &'_ mut T {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Write::write(*self, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Self::Error> {
+        Write::write_vectored(*self, bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Write::flush(*self)
+    }
+
+    #[inline]
+    fn close(&mut self) -> Result<(), Self::Error> {
+        Write::close(*self)
+    }
+}
+
+impl Write for &'_ mut [u8] {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        crate::slice_write(self, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn close(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+pub trait Seek {
+    type Error;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error>;
+
+    fn rewind(&mut self) -> Result<(), Self::Error> {
+        self.seek(SeekFrom::Start(0)).map(drop)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, Self::Error> {
+        self.seek(SeekFrom::Current(0))
+    }
+}
+
+impl<T: ?Sized + Seek> Seek for &'_ mut T {
+    type Error = T::Error;
+
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+        Seek::seek(*self, pos)
+    }
+}
+
+impl Write for crate::Sink {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn close(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uWrite for crate::Sink {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn write_str(&mut self, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Read for crate::Empty {
+    #[inline]
+    fn read(&mut self, _: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+}
+
+impl Read for crate::Repeat {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        for b in &mut *buf {
+            *b = self.0
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+        buf.fill(self.0);
+        Ok(())
+    }
+}
+
+impl<S: Read> Read for Take<S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let buf = match buf.get_mut(..self.limit) {
+            Some(buf) => buf,
+            None => buf,
+        };
+        let res = self.stream.read(buf);
+        if let Ok(len) = &res {
+            self.limit -= len;
+        }
+        res
+    }
+
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<(), Self::Error> {
+        let stream = &mut self.stream;
+        let len = buf.with_limit(self.limit, |buf| {
+            stream.read_buf(buf).map(|()| buf.filled().len())
+        })?;
+        self.limit -= len;
+        Ok(())
+    }
+}
+
+impl<S: Write> Write for Take<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if self.limit == 0 && !buf.is_empty() {
+            return Ok(0)
+        }
+
+        let buf = match buf.get(..self.limit) {
+            Some(buf) => buf,
+            None => buf,
+        };
+        let res = self.stream.write(buf);
+        if let Ok(len) = &res {
+            self.limit -= len;
+        }
+        res
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.stream.flush()
+    }
+
+    fn close(&mut self) -> Result<(), Self::Error> {
+        self.stream.close()
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<W: ?Sized + Write> ufmt::uWrite for uWriter<W> {
+    type Error = AllError<W::Error>;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.inner.write_all(s.as_bytes())
+    }
+}
+
+pub trait ReadExt: Read {
+    fn copy_to<W: Write>(&mut self, write: W) -> Result<usize, AllError<Self::Error>> where Self::Error: From<W::Error> {
+        copy(self, write)
+    }
+
+    fn chain<B: Read>(self, next: B) -> Chain<Self, B> where Self: Sized {
+        Chain::new(self, next)
+    }
+
+    fn fuse(self) -> Fuse<Self> where Self: Sized {
+        Fuse::new(self)
+    }
+
+    fn bytes(self) -> Bytes<Self> where Self: Sized {
+        Bytes::new(self)
+    }
+
+    fn chars(self) -> Chars<Self> where Self: Sized {
+        Chars::new(self)
+    }
+}
+
+impl<T: ?Sized + Read> ReadExt for T { }
+
+pub trait WriteExt: Write {
+    fn copy_from<R: Read>(&mut self, read: R) -> Result<usize, AllError<Self::Error>> where Self::Error: From<R::Error> {
+        copy(read, self)
+    }
+}
+
+impl<T: ?Sized + Write> WriteExt for T { }
+
+pub fn copy<R: Read, W: Write, E>(mut read: R, mut write: W) -> Result<usize, AllError<E>> where E: From<R::Error> + From<W::Error> {
+    let mut buf = [0u8; 0x10];
+    let mut total = 0usize;
+    loop {
+        let len = match read.read(&mut buf) {
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(AllError::from_io(e, total)),
+            Ok(0) => break,
+            Ok(len) => len,
+        };
+        let buf = unsafe {
+            debug_assert!(len <= buf.len());
+            buf.get_unchecked(..len)
+        };
+        match write.write_all(buf) {
+            Ok(()) => (),
+            Err(AllError::Io(e)) => return Err(AllError::Io(e.into())),
+            Err(AllError::WouldBlock(n)) => return Err(AllError::WouldBlock(total.saturating_add(n))),
+            Err(_) => return Err(AllError::WriteZero),
+        }
+        total = total.saturating_add(len);
+    }
+
+    Ok(total)
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum WriteFmtError<E> {
+    FormatterError,
+    Io(E),
+}
+
+impl<E: fmt::Display> fmt::Display for WriteFmtError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteFmtError::FormatterError => write!(f, "formatter error"),
+            WriteFmtError::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: IoError> IoError for WriteFmtError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            WriteFmtError::FormatterError => ErrorKind::Other,
+            WriteFmtError::Io(err) => err.kind(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for WriteFmtError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WriteFmtError::FormatterError => None,
+            WriteFmtError::Io(err) => Some(err)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_impl {
+    use std::io::{Read, Write, Seek, SeekFrom, IoSlice, IoSliceMut, Error, ErrorKind};
+    use crate::io_slice;
+
+    pub struct StdCompat<T: ?Sized>(pub T);
+
+    impl<T: ?Sized> StdCompat<T> {
+        #[inline]
+        pub fn inner_mut(&mut self) -> &mut T {
+            &mut self.0
+        }
+    }
+
+    impl<T: ?Sized> crate::ErrorType for StdCompat<T> {
+        type Error = Error;
+    }
+
+    impl<T: ?Sized + Read> super::Read for StdCompat<T> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.inner_mut().read(buf)
+        }
+
+        /// `std::io::Read::read_buf` is not stable yet, so the unfilled part
+        /// is zeroed and handed to `read`. `ReadBuf` remembers what it has
+        /// initialized, so a buffer reused across calls is only zeroed once.
+        fn read_buf(&mut self, buf: &mut crate::ReadBuf<'_>) -> Result<(), Self::Error> {
+            let len = self.inner_mut().read(buf.initialize_unfilled())?;
+            buf.advance(len);
+            Ok(())
+        }
+
+        #[inline]
+        fn read_vectored(&mut self, bufs: &mut [crate::IoSliceMut<'_>]) -> Result<usize, Self::Error> {
+            self.inner_mut().read_vectored(io_slice::to_std_mut(bufs))
+        }
+    }
+
+    impl<T: ?Sized + Write> super::Write for StdCompat<T> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            match self.inner_mut().write(buf) {
+                Ok(0) if buf.is_empty() => Ok(0),
+                Ok(n) if n == 0 || n > buf.len() => Err(Error::new(ErrorKind::WriteZero, "Invalid write length")),
+                res => res,
+            }
+        }
+
+        fn write_vectored(&mut self, bufs: &[crate::IoSlice<'_>]) -> Result<usize, Self::Error> {
+            let len = bufs.iter().map(|b| b.len()).sum::<usize>();
+            match self.inner_mut().write_vectored(io_slice::to_std(bufs)) {
+                Ok(0) if len == 0 => Ok(0),
+                Ok(n) if n == 0 || n > len => Err(Error::new(ErrorKind::WriteZero, "Invalid write length")),
+                res => res,
+            }
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.inner_mut().flush()
+        }
+
+        /// `std::io::Write` has no notion of closing, so this only flushes.
+        #[inline]
+        fn close(&mut self) -> Result<(), Self::Error> {
+            self.inner_mut().flush()
+        }
+    }
+
+    impl<T: ?Sized + Seek> super::Seek for StdCompat<T> {
+        type Error = Error;
+
+        #[inline]
+        fn seek(&mut self, pos: crate::SeekFrom) -> Result<u64, Self::Error> {
+            self.inner_mut().seek(pos.into())
+        }
+    }
+
+    impl<T: ?Sized + super::Read> Read for StdCompat<T> where
+        T::Error: Into<Error>,
+    {
+        // `read_buf` would forward to `super::Read::read_buf`, but overriding
+        // it needs the unstable `read_buf` feature.
+
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            self.inner_mut().read(buf)
+                .map_err(Into::into)
+        }
+
+        #[inline]
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Error> {
+            self.inner_mut().read_vectored(io_slice::from_std_mut(bufs))
+                .map_err(Into::into)
+        }
+    }
+
+    impl<T: ?Sized + super::Write> Write for StdCompat<T> where
+        T::Error: Into<Error>,
+    {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+            self.inner_mut().write(buf)
+                .map_err(Into::into)
+        }
+
+        #[inline]
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Error> {
+            self.inner_mut().write_vectored(io_slice::from_std(bufs))
+                .map_err(Into::into)
+        }
+
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.inner_mut().write_all(buf)
+                .map_err(Into::into)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<(), Error> {
+            self.inner_mut().flush()
+                .map_err(Into::into)
+        }
+    }
+
+    impl<T: ?Sized + super::Seek> Seek for StdCompat<T> where
+        T::Error: Into<Error>,
+    {
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+            self.inner_mut().seek(pos.into())
+                .map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use std_impl::StdCompat;
@@ -0,0 +1,143 @@
+use crate::{AllError, ErrorType, IoError, ErrorKind};
+
+pub trait ReadAt: ErrorType {
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    fn read_exact_at(&mut self, mut offset: u64, mut buf: &mut [u8]) -> Result<(), AllError<Self::Error>> {
+        let len = buf.len();
+        while !buf.is_empty() {
+            match self.read_at(offset, buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let tmp = buf;
+                    buf = &mut tmp[n..];
+                    offset = offset.saturating_add(n as u64);
+                },
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(AllError::from_io(e, len - buf.len())),
+            }
+        }
+        if !buf.is_empty() {
+            Err(AllError::UnexpectedEof)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub trait WriteAt: ErrorType {
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize, Self::Error>;
+
+    fn flush_at(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn write_all_at(&mut self, mut offset: u64, mut buf: &[u8]) -> Result<(), AllError<Self::Error>> {
+        let len = buf.len();
+        while !buf.is_empty() {
+            match self.write_at(offset, buf) {
+                Ok(0) => return Err(AllError::WriteZero),
+                Ok(n) => {
+                    buf = buf.get(n..).unwrap_or(&[]);
+                    offset = offset.saturating_add(n as u64);
+                },
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => return Err(AllError::from_io(e, len - buf.len())),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: ?Sized + ReadAt> ReadAt for &'_ mut T {
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        ReadAt::read_at(*self, offset, buf)
+    }
+}
+
+impl<T: ?Sized + WriteAt> WriteAt for &'_ mut T {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize, Self::Error> {
+        WriteAt::write_at(*self, offset, buf)
+    }
+
+    #[inline]
+    fn flush_at(&mut self) -> Result<(), Self::Error> {
+        WriteAt::flush_at(*self)
+    }
+}
+
+impl ReadAt for [u8] {
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let offset = crate::slice_offset(self.len(), offset);
+        let mut rem = unsafe { self.get_unchecked(offset..) };
+        Ok(crate::slice_read(&mut rem, buf))
+    }
+}
+
+impl ReadAt for &'_ [u8] {
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let offset = crate::slice_offset(self.len(), offset);
+        let mut rem = unsafe { self.get_unchecked(offset..) };
+        Ok(crate::slice_read(&mut rem, buf))
+    }
+}
+
+impl WriteAt for [u8] {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize, Self::Error> {
+        let offset = crate::slice_offset(self.len(), offset);
+        let mut rem = unsafe { self.get_unchecked_mut(offset..) };
+        crate::slice_write(&mut rem, buf)
+    }
+}
+
+#[cfg(all(feature = "std", any(unix, windows)))]
+mod std_impl {
+    use std::fs::File;
+    use std::io::Error;
+    #[cfg(unix)]
+    use std::os::unix::fs::FileExt;
+    #[cfg(windows)]
+    use std::os::windows::fs::FileExt;
+
+    impl crate::ErrorType for File {
+        type Error = Error;
+    }
+
+    impl super::ReadAt for File {
+        #[cfg(unix)]
+        #[inline]
+        fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            FileExt::read_at(self, buf, offset)
+        }
+
+        #[cfg(windows)]
+        #[inline]
+        fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            FileExt::seek_read(self, buf, offset)
+        }
+    }
+
+    impl super::WriteAt for File {
+        #[cfg(unix)]
+        #[inline]
+        fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize, Self::Error> {
+            FileExt::write_at(self, buf, offset)
+        }
+
+        #[cfg(windows)]
+        #[inline]
+        fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize, Self::Error> {
+            FileExt::seek_write(self, buf, offset)
+        }
+
+        #[inline]
+        fn flush_at(&mut self) -> Result<(), Self::Error> {
+            std::io::Write::flush(self)
+        }
+    }
+}
@@ -0,0 +1,617 @@
+use core::fmt;
+use crate::AllError;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+/// What to do when the secondary stream of a `TeeReader` or `Broadcast` fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum TeePolicy {
+    /// Return the error as `TeeError::Secondary`.
+    FailFast,
+    /// Drop the error and carry on as if the data had been mirrored.
+    IgnoreSecondary,
+}
+
+impl Default for TeePolicy {
+    #[inline]
+    fn default() -> Self {
+        TeePolicy::FailFast
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum TeeError<P, S> {
+    Primary(P),
+    Secondary(S),
+}
+
+impl<P: fmt::Display, S: fmt::Display> fmt::Display for TeeError<P, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TeeError::Primary(e) => fmt::Display::fmt(e, f),
+            TeeError::Secondary(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<P: ufmt::uDisplay, S: ufmt::uDisplay> ufmt::uDisplay for TeeError<P, S> {
+    fn fmt<W: ?Sized + ufmt::uWrite>(&self, f: &mut ufmt::Formatter<W>) -> Result<(), W::Error> {
+        match self {
+            TeeError::Primary(e) => ufmt::uDisplay::fmt(e, f),
+            TeeError::Secondary(e) => ufmt::uDisplay::fmt(e, f),
+        }
+    }
+}
+
+impl<P: crate::IoError, S: crate::IoError> crate::IoError for TeeError<P, S> {
+    fn kind(&self) -> crate::ErrorKind {
+        match self {
+            TeeError::Primary(e) => e.kind(),
+            TeeError::Secondary(e) => e.kind(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P: StdError + 'static, S: StdError + 'static> StdError for TeeError<P, S> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            TeeError::Primary(e) => Some(e),
+            TeeError::Secondary(e) => Some(e),
+        }
+    }
+}
+
+/// Mirrors everything read from `reader` into `writer`.
+///
+/// The `AsyncRead` impl requires an `AsyncBufRead` reader, so that data can
+/// stay buffered there until the writer has accepted it. The `Read` impl
+/// cannot hold data back, so with `TeePolicy::FailFast` a secondary error is
+/// returned by the next call, after the data it failed to mirror.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct TeeReader<R, W: crate::ErrorType> {
+    reader: R,
+    writer: W,
+    policy: TeePolicy,
+    pending: Option<AllError<W::Error>>,
+    #[cfg(feature = "async")]
+    mirrored: usize,
+}
+
+impl<R, W: crate::ErrorType> TeeReader<R, W> {
+    pub const fn new(reader: R, writer: W) -> Self {
+        Self::with_policy(reader, writer, TeePolicy::FailFast)
+    }
+
+    pub const fn with_policy(reader: R, writer: W, policy: TeePolicy) -> Self {
+        Self {
+            reader,
+            writer,
+            policy,
+            pending: None,
+            #[cfg(feature = "async")]
+            mirrored: 0,
+        }
+    }
+
+    #[inline]
+    pub fn policy(&self) -> TeePolicy {
+        self.policy
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> (&R, &W) {
+        (&self.reader, &self.writer)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> (&mut R, &mut W) {
+        (&mut self.reader, &mut self.writer)
+    }
+
+    /// Any secondary error that has not been returned yet is discarded.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+/// Duplicates every write to `primary` into `secondary`.
+///
+/// Only what `primary` accepts is passed on to `secondary`. With
+/// `TeePolicy::FailFast`, a secondary error is returned by the next call, as
+/// the write that caused it has already reached `primary`.
+#[cfg(feature = "sync")]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Broadcast<A, B: crate::ErrorType> {
+    primary: A,
+    secondary: B,
+    policy: TeePolicy,
+    pending: Option<AllError<B::Error>>,
+}
+
+#[cfg(feature = "sync")]
+impl<A, B: crate::ErrorType> Broadcast<A, B> {
+    pub const fn new(primary: A, secondary: B) -> Self {
+        Self::with_policy(primary, secondary, TeePolicy::FailFast)
+    }
+
+    pub const fn with_policy(primary: A, secondary: B, policy: TeePolicy) -> Self {
+        Self {
+            primary,
+            secondary,
+            policy,
+            pending: None,
+        }
+    }
+
+    #[inline]
+    pub fn policy(&self) -> TeePolicy {
+        self.policy
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.primary, &self.secondary)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.primary, &mut self.secondary)
+    }
+
+    /// Any secondary error that has not been returned yet is discarded.
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+}
+
+/// Duplicates every write to `primary` into `secondary`, asynchronously.
+///
+/// Writes at most `N` bytes at a time and keeps a copy of what `primary`
+/// accepted, which is handed to `secondary` before the next write, flush or
+/// close.
+#[cfg(feature = "async")]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct AsyncBroadcast<A, B, const N: usize = 64> {
+    primary: A,
+    secondary: B,
+    policy: TeePolicy,
+    backlog: [u8; N],
+    filled: usize,
+    mirrored: usize,
+}
+
+#[cfg(feature = "async")]
+impl<A, B> AsyncBroadcast<A, B> {
+    pub const fn new(primary: A, secondary: B) -> Self {
+        Self::with_policy(primary, secondary, TeePolicy::FailFast)
+    }
+
+    pub const fn with_policy(primary: A, secondary: B, policy: TeePolicy) -> Self {
+        Self::with_backlog(primary, secondary, policy)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<A, B, const N: usize> AsyncBroadcast<A, B, N> {
+    /// Like `with_policy`, but with a backlog of `N` bytes instead of the default.
+    pub const fn with_backlog(primary: A, secondary: B, policy: TeePolicy) -> Self {
+        const { assert!(N != 0, "the backlog must hold at least one byte") };
+        Self {
+            primary,
+            secondary,
+            policy,
+            backlog: [0; N],
+            filled: 0,
+            mirrored: 0,
+        }
+    }
+
+    #[inline]
+    pub fn policy(&self) -> TeePolicy {
+        self.policy
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.primary, &self.secondary)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.primary, &mut self.secondary)
+    }
+
+    /// Anything `primary` accepted but `secondary` has not been given yet is
+    /// discarded.
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+}
+
+/// A secondary stream that stops accepting data fails with `AllError::WriteZero`.
+impl<R: crate::ErrorType, W: crate::ErrorType> crate::ErrorType for TeeReader<R, W> {
+    type Error = TeeError<R::Error, AllError<W::Error>>;
+}
+
+#[cfg(feature = "sync")]
+impl<A: crate::ErrorType, B: crate::ErrorType> crate::ErrorType for Broadcast<A, B> {
+    type Error = TeeError<A::Error, AllError<B::Error>>;
+}
+
+#[cfg(feature = "async")]
+impl<A: crate::ErrorType, B: crate::ErrorType, const N: usize> crate::ErrorType for AsyncBroadcast<A, B, N> {
+    type Error = TeeError<A::Error, AllError<B::Error>>;
+}
+
+#[cfg(feature = "sync")]
+mod sync_impl {
+    use crate::AllError;
+    use super::{TeeReader, Broadcast, TeeError, TeePolicy};
+
+    impl<R: crate::Read, W: crate::Write> crate::Read for TeeReader<R, W> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if let Some(e) = self.pending.take() {
+                return Err(TeeError::Secondary(e))
+            }
+
+            let len = self.reader.read(buf).map_err(TeeError::Primary)?;
+            let data = unsafe {
+                debug_assert!(len <= buf.len());
+                buf.get_unchecked(..len)
+            };
+            if let Err(e) = self.writer.write_all(data) {
+                if self.policy == TeePolicy::FailFast {
+                    // `buf` already holds the data, so it is returned first
+                    self.pending = Some(e);
+                }
+            }
+            Ok(len)
+        }
+    }
+
+    impl<A: crate::Write, B: crate::Write> Broadcast<A, B> {
+        fn take_pending(&mut self) -> Result<(), <Self as crate::ErrorType>::Error> {
+            match self.pending.take() {
+                Some(e) => Err(TeeError::Secondary(e)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl<A: crate::Write, B: crate::Write> crate::Write for Broadcast<A, B> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.take_pending()?;
+
+            let len = self.primary.write(buf).map_err(TeeError::Primary)?;
+            let data = buf.get(..len).unwrap_or(buf);
+            if let Err(e) = self.secondary.write_all(data) {
+                if self.policy == TeePolicy::FailFast {
+                    // `primary` already has the data, so its count is returned first
+                    self.pending = Some(e);
+                }
+            }
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.take_pending()?;
+            self.primary.flush().map_err(TeeError::Primary)?;
+            match self.secondary.flush() {
+                Err(e) if self.policy == TeePolicy::FailFast => Err(TeeError::Secondary(AllError::Io(e))),
+                _ => Ok(()),
+            }
+        }
+
+        fn close(&mut self) -> Result<(), Self::Error> {
+            self.take_pending()?;
+            self.primary.close().map_err(TeeError::Primary)?;
+            match self.secondary.close() {
+                Err(e) if self.policy == TeePolicy::FailFast => Err(TeeError::Secondary(AllError::Io(e))),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use core::task::{Context, Poll};
+    use core::pin::Pin;
+    use core::cmp;
+    use crate::AllError;
+    use super::{TeeReader, AsyncBroadcast, TeeError, TeePolicy};
+
+    impl<R: crate::AsyncBufRead, W: crate::AsyncWrite> crate::AsyncRead for TeeReader<R, W> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize, Self::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            // left behind by the `Read` impl
+            if let Some(e) = this.pending.take() {
+                return Poll::Ready(Err(TeeError::Secondary(e)))
+            }
+            let mut reader = unsafe { Pin::new_unchecked(&mut this.reader) };
+            let mut writer = unsafe { Pin::new_unchecked(&mut this.writer) };
+
+            let available = match reader.as_mut().poll_fill_buf(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(TeeError::Primary(e))),
+                Poll::Ready(Ok(available)) => available,
+            };
+            // anything already mirrored is still at the front of the reader's buffer
+            let want = cmp::min(available.len(), buf.len());
+            while this.mirrored < want {
+                let data = unsafe { available.get_unchecked(this.mirrored..want) };
+                match writer.as_mut().poll_write(cx, data) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(n)) if n > 0 => this.mirrored += n,
+                    Poll::Ready(res) => match this.policy {
+                        TeePolicy::FailFast => return Poll::Ready(Err(TeeError::Secondary(match res {
+                            Err(e) => AllError::Io(e),
+                            Ok(_) => AllError::WriteZero,
+                        }))),
+                        TeePolicy::IgnoreSecondary => this.mirrored = want,
+                    },
+                }
+            }
+
+            let len = cmp::min(this.mirrored, want);
+            unsafe {
+                buf.get_unchecked_mut(..len).copy_from_slice(available.get_unchecked(..len));
+            }
+            reader.consume(len);
+            this.mirrored -= len;
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    impl<A: crate::AsyncWrite, B: crate::AsyncWrite, const N: usize> AsyncBroadcast<A, B, N> {
+        /// Hands whatever `primary` accepted earlier over to `secondary`.
+        fn poll_mirror(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), <Self as crate::ErrorType>::Error>> {
+            let this = unsafe { self.get_unchecked_mut() };
+            let mut secondary = unsafe { Pin::new_unchecked(&mut this.secondary) };
+
+            while this.mirrored < this.filled {
+                let data = unsafe { this.backlog.get_unchecked(this.mirrored..this.filled) };
+                match secondary.as_mut().poll_write(cx, data) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(n)) if n > 0 => this.mirrored += cmp::min(n, data.len()),
+                    Poll::Ready(res) => match this.policy {
+                        TeePolicy::FailFast => {
+                            this.filled = 0;
+                            this.mirrored = 0;
+                            return Poll::Ready(Err(TeeError::Secondary(match res {
+                                Err(e) => AllError::Io(e),
+                                Ok(_) => AllError::WriteZero,
+                            })))
+                        },
+                        TeePolicy::IgnoreSecondary => this.mirrored = this.filled,
+                    },
+                }
+            }
+
+            this.filled = 0;
+            this.mirrored = 0;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<A: crate::AsyncWrite, B: crate::AsyncWrite, const N: usize> crate::AsyncWrite for AsyncBroadcast<A, B, N> {
+        fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+            // nothing new reaches `primary` until `secondary` has caught up
+            match self.as_mut().poll_mirror(cx)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(()) => (),
+            }
+
+            let this = unsafe { self.get_unchecked_mut() };
+            let primary = unsafe { Pin::new_unchecked(&mut this.primary) };
+            let buf = unsafe { buf.get_unchecked(..cmp::min(buf.len(), N)) };
+
+            let len = match primary.poll_write(cx, buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(TeeError::Primary(e))),
+                Poll::Ready(Ok(len)) => cmp::min(len, buf.len()),
+            };
+            unsafe {
+                this.backlog.get_unchecked_mut(..len).copy_from_slice(buf.get_unchecked(..len));
+            }
+            this.filled = len;
+            Poll::Ready(Ok(len))
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            match self.as_mut().poll_mirror(cx)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(()) => (),
+            }
+
+            let this = unsafe { self.get_unchecked_mut() };
+            let primary = unsafe { Pin::new_unchecked(&mut this.primary) };
+            let secondary = unsafe { Pin::new_unchecked(&mut this.secondary) };
+
+            match primary.poll_flush(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(TeeError::Primary(e))),
+                Poll::Ready(Ok(())) => (),
+            }
+            match secondary.poll_flush(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(Err(e)) if this.policy == TeePolicy::FailFast => Poll::Ready(Err(TeeError::Secondary(AllError::Io(e)))),
+                Poll::Ready(_) => Poll::Ready(Ok(())),
+            }
+        }
+
+        fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+            match self.as_mut().poll_mirror(cx)? {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(()) => (),
+            }
+
+            let this = unsafe { self.get_unchecked_mut() };
+            let primary = unsafe { Pin::new_unchecked(&mut this.primary) };
+            let secondary = unsafe { Pin::new_unchecked(&mut this.secondary) };
+
+            match primary.poll_close(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(TeeError::Primary(e))),
+                Poll::Ready(Ok(())) => (),
+            }
+            match secondary.poll_close(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(Err(e)) if this.policy == TeePolicy::FailFast => Poll::Ready(Err(TeeError::Secondary(AllError::Io(e)))),
+                Poll::Ready(_) => Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErrorKind;
+
+    /// Accepts at most `room` bytes; with `stall` set, every other write is
+    /// `Pending` first.
+    struct Sink {
+        data: [u8; 8],
+        len: usize,
+        room: usize,
+        stall: bool,
+        stalled: bool,
+    }
+
+    impl Sink {
+        fn new(room: usize) -> Self {
+            Self { data: [0; 8], len: 0, room, stall: false, stalled: false }
+        }
+
+        fn stalling(room: usize) -> Self {
+            Self { stall: true, ..Self::new(room) }
+        }
+
+        fn data(&self) -> &[u8] {
+            &self.data[..self.len]
+        }
+
+        fn put(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+            let n = buf.len().min(self.room - self.len);
+            if n == 0 && !buf.is_empty() {
+                return Err(ErrorKind::BrokenPipe)
+            }
+            self.data[self.len..self.len + n].copy_from_slice(&buf[..n]);
+            self.len += n;
+            Ok(n)
+        }
+    }
+
+    impl crate::ErrorType for Sink {
+        type Error = ErrorKind;
+    }
+
+    #[cfg(feature = "sync")]
+    mod sync_tests {
+        use crate::{AllError, Read, Write};
+        use super::super::{TeeReader, Broadcast, TeeError, TeePolicy};
+        use super::{Sink, ErrorKind};
+
+        impl Write for Sink {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                self.put(buf)
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn tee_reader_returns_the_data_before_the_error() {
+            let mut tee = TeeReader::new(&b"abcd"[..], Sink::new(2));
+            let mut buf = [0u8; 4];
+            assert_eq!(tee.read(&mut buf), Ok(4));
+            assert_eq!(&buf, b"abcd");
+            assert_eq!(tee.read(&mut buf), Err(TeeError::Secondary(AllError::Io(ErrorKind::BrokenPipe))));
+            assert_eq!(tee.read(&mut buf), Ok(0));
+            assert_eq!(tee.get_ref().1.data(), b"ab");
+        }
+
+        #[test]
+        fn broadcast_returns_the_count_before_the_error() {
+            let mut broadcast = Broadcast::new(Sink::new(8), Sink::new(2));
+            assert_eq!(broadcast.write(b"abcd"), Ok(4));
+            assert_eq!(broadcast.flush(), Err(TeeError::Secondary(AllError::Io(ErrorKind::BrokenPipe))));
+            assert_eq!(broadcast.flush(), Ok(()));
+            let (primary, secondary) = broadcast.get_ref();
+            assert_eq!(primary.data(), b"abcd");
+            assert_eq!(secondary.data(), b"ab");
+        }
+
+        #[test]
+        fn broadcast_can_ignore_the_secondary() {
+            let mut broadcast = Broadcast::with_policy(Sink::new(8), Sink::new(2), TeePolicy::IgnoreSecondary);
+            assert_eq!(broadcast.write_all(b"abcd"), Ok(()));
+            assert_eq!(broadcast.flush(), Ok(()));
+            assert_eq!(broadcast.get_ref().0.data(), b"abcd");
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod async_tests {
+        use core::task::{Context, Poll, Waker};
+        use core::pin::Pin;
+        use crate::{AllError, AsyncWrite};
+        use super::super::{AsyncBroadcast, TeeError, TeePolicy};
+        use super::{Sink, ErrorKind};
+
+        impl AsyncWrite for Sink {
+            fn poll_write(self: Pin<&mut Self>, _: &mut Context, buf: &[u8]) -> Poll<Result<usize, Self::Error>> {
+                let this = self.get_mut();
+                if this.stall && !this.stalled {
+                    this.stalled = true;
+                    return Poll::Pending
+                }
+                this.stalled = false;
+                Poll::Ready(this.put(buf))
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        #[test]
+        fn backlog_is_replayed_before_the_next_write() {
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut broadcast = AsyncBroadcast::<_, _, 2>::with_backlog(Sink::new(8), Sink::stalling(8), TeePolicy::FailFast);
+            let mut broadcast = Pin::new(&mut broadcast);
+            assert_eq!(broadcast.as_mut().poll_write(&mut cx, b"abc"), Poll::Ready(Ok(2)));
+            assert_eq!(broadcast.as_mut().poll_write(&mut cx, b"c"), Poll::Pending);
+            assert_eq!(broadcast.get_ref().0.data(), b"ab");
+            assert_eq!(broadcast.as_mut().poll_write(&mut cx, b"c"), Poll::Ready(Ok(1)));
+            assert_eq!(broadcast.as_mut().poll_close(&mut cx), Poll::Pending);
+            assert_eq!(broadcast.as_mut().poll_close(&mut cx), Poll::Ready(Ok(())));
+            let (primary, secondary) = broadcast.get_ref();
+            assert_eq!(primary.data(), b"abc");
+            assert_eq!(secondary.data(), b"abc");
+        }
+
+        #[test]
+        fn backlog_errors_surface_on_the_next_call() {
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut broadcast = AsyncBroadcast::new(Sink::new(8), Sink::new(2));
+            let mut broadcast = Pin::new(&mut broadcast);
+            assert_eq!(broadcast.as_mut().poll_write(&mut cx, b"abcd"), Poll::Ready(Ok(4)));
+            assert_eq!(broadcast.as_mut().poll_flush(&mut cx), Poll::Ready(Err(TeeError::Secondary(AllError::Io(ErrorKind::BrokenPipe)))));
+            assert_eq!(broadcast.as_mut().poll_flush(&mut cx), Poll::Ready(Ok(())));
+        }
+    }
+}